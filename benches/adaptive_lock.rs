@@ -0,0 +1,83 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    hint::black_box,
+    thread,
+    time::{Duration, Instant},
+};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use omango_sync::{adaptive_lock::AdaptiveLock, mutex::Mutex};
+
+const THREADS: usize = 4;
+
+/// Burns roughly `work` iterations inside the critical section.
+#[inline(never)]
+fn hold(work: u64) {
+    for i in 0..work {
+        black_box(i);
+    }
+}
+
+/// Runs `iters` critical sections per thread and returns the average latency of one.
+fn run<L: Fn() + Sync>(iters: u64, lock: L) -> Duration {
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for _ in 0..THREADS {
+            scope.spawn(|| {
+                for _ in 0..iters {
+                    lock();
+                }
+            });
+        }
+    });
+    start.elapsed() / THREADS as u32
+}
+
+fn workloads(c: &mut Criterion) {
+    // The short hold is well under the spin threshold, the long one well above.
+    for (name, work) in [("short_hold", 50), ("long_hold", 50_000)] {
+        let mut group = c.benchmark_group(name);
+        if work > 1_000 {
+            group.sample_size(10);
+        }
+        // The futex mutex spins a fixed number of times before parking.
+        group.bench_with_input(BenchmarkId::new("static_spin", work), &work, |b, &work| {
+            let lock = Mutex::new(());
+            b.iter_custom(|iters| run(iters, || {
+                let _guard = lock.lock();
+                hold(work);
+            }))
+        });
+        group.bench_with_input(BenchmarkId::new("adaptive", work), &work, |b, &work| {
+            let lock = AdaptiveLock::new((), 100);
+            b.iter_custom(|iters| run(iters, || {
+                let _guard = lock.lock();
+                hold(work);
+            }))
+        });
+        group.finish();
+    }
+}
+
+criterion_group!(benches, workloads);
+criterion_main!(benches);
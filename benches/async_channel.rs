@@ -0,0 +1,98 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+use std::time::{Duration, Instant};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use tokio::runtime::{Builder, Runtime};
+
+use omango_sync::async_channel::async_bounded;
+
+const CAPACITY: usize = 128;
+
+fn runtime() -> Runtime {
+    Builder::new_current_thread().build().unwrap()
+}
+
+/// Sends `iters` items from each producer task to one consumer on a single-threaded
+/// runtime, so only the channel itself is measured, and returns the wall time.
+fn omango(producers: u64, iters: u64) -> Duration {
+    let runtime = runtime();
+    let start = Instant::now();
+    runtime.block_on(async {
+        let (sender, mut receiver) = async_bounded(CAPACITY);
+        for _ in 0..producers {
+            let sender = sender.clone();
+            runtime.spawn(async move {
+                for i in 0..iters {
+                    sender.send(i).await.unwrap();
+                }
+            });
+        }
+        drop(sender);
+
+        let mut sum = 0;
+        while let Some(i) = receiver.recv().await {
+            sum += i;
+        }
+        assert_eq!(sum, producers * iters * iters.saturating_sub(1) / 2);
+    });
+    start.elapsed()
+}
+
+/// The same as [`omango`] with the channel of tokio.
+fn tokio_mpsc(producers: u64, iters: u64) -> Duration {
+    let runtime = runtime();
+    let start = Instant::now();
+    runtime.block_on(async {
+        let (sender, mut receiver) = tokio::sync::mpsc::channel(CAPACITY);
+        for _ in 0..producers {
+            let sender = sender.clone();
+            runtime.spawn(async move {
+                for i in 0..iters {
+                    sender.send(i).await.unwrap();
+                }
+            });
+        }
+        drop(sender);
+
+        let mut sum = 0;
+        while let Some(i) = receiver.recv().await {
+            sum += i;
+        }
+        assert_eq!(sum, producers * iters * iters.saturating_sub(1) / 2);
+    });
+    start.elapsed()
+}
+
+fn channels(c: &mut Criterion) {
+    let mut group = c.benchmark_group("async_mpsc_throughput");
+    for producers in [1, 4] {
+        group.bench_with_input(BenchmarkId::new("omango", producers), &producers, |b, &producers| {
+            b.iter_custom(|iters| omango(producers, iters))
+        });
+        group.bench_with_input(BenchmarkId::new("tokio", producers), &producers, |b, &producers| {
+            b.iter_custom(|iters| tokio_mpsc(producers, iters))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, channels);
+criterion_main!(benches);
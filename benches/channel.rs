@@ -0,0 +1,83 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use omango_sync::channel::bounded;
+
+const CAPACITY: usize = 128;
+
+/// Sends `iters` items from each producer to one consumer and returns the wall time.
+fn run<S, R>(producers: u64, iters: u64, sender: S, recv: R) -> Duration
+where
+    S: Fn(u64) + Sync,
+    R: Fn() -> u64,
+{
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for _ in 0..producers {
+            scope.spawn(|| {
+                for i in 0..iters {
+                    sender(i);
+                }
+            });
+        }
+        let mut sum = 0;
+        for _ in 0..producers * iters {
+            sum += recv();
+        }
+        assert_eq!(sum, producers * iters * iters.saturating_sub(1) / 2);
+    });
+    start.elapsed()
+}
+
+fn channels(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mpsc_throughput");
+    for producers in [1, 4] {
+        group.bench_with_input(BenchmarkId::new("omango", producers), &producers, |b, &producers| {
+            b.iter_custom(|iters| {
+                let (sender, receiver) = bounded(CAPACITY);
+                run(producers, iters, |i| sender.send(i).unwrap(), || receiver.recv().unwrap())
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("std_bounded", producers), &producers, |b, &producers| {
+            b.iter_custom(|iters| {
+                let (sender, receiver) = mpsc::sync_channel(CAPACITY);
+                run(producers, iters, |i| sender.send(i).unwrap(), || receiver.recv().unwrap())
+            })
+        });
+        group.bench_with_input(BenchmarkId::new("std_unbounded", producers), &producers, |b, &producers| {
+            b.iter_custom(|iters| {
+                let (sender, receiver) = mpsc::channel();
+                run(producers, iters, |i| sender.send(i).unwrap(), || receiver.recv().unwrap())
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, channels);
+criterion_main!(benches);
@@ -0,0 +1,63 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    hint::black_box,
+    thread,
+    time::{Duration, Instant},
+};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use omango_util::lock::RwSpinlock;
+
+use omango_sync::mcs_lock::MCSLock;
+
+/// Runs `iters` critical sections on `threads` threads and returns the wall time.
+fn run<R, L: Fn() -> R + Sync>(threads: usize, iters: u64, lock: L) -> Duration {
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                for _ in 0..iters {
+                    lock();
+                }
+            });
+        }
+    });
+    start.elapsed()
+}
+
+fn locks(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mcs_vs_spinlock");
+    for threads in [1, 2, 4, 8] {
+        group.bench_with_input(BenchmarkId::new("mcs", threads), &threads, |b, &threads| {
+            let lock = MCSLock::new();
+            b.iter_custom(|iters| run(threads, iters, || lock.with_lock(|| black_box(0))))
+        });
+        group.bench_with_input(BenchmarkId::new("spinlock", threads), &threads, |b, &threads| {
+            let lock = RwSpinlock::new(());
+            b.iter_custom(|iters| run(threads, iters, || drop(black_box(lock.write()))))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, locks);
+criterion_main!(benches);
@@ -0,0 +1,72 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use omango_sync::padded::PaddedAtomic;
+
+const SLOTS: usize = 64;
+
+/// Every thread increments its own slot `iters` times and the wall time is returned.
+fn run<F: Fn(usize) + Sync>(threads: usize, iters: u64, increment: F) -> Duration {
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for id in 0..threads {
+            let increment = &increment;
+            scope.spawn(move || {
+                for _ in 0..iters {
+                    increment(id);
+                }
+            });
+        }
+    });
+    start.elapsed()
+}
+
+fn false_sharing(c: &mut Criterion) {
+    // False sharing only shows when the threads really run in parallel.
+    let threads = thread::available_parallelism()
+        .map_or(8, |n| n.get())
+        .clamp(2, SLOTS);
+
+    let mut group = c.benchmark_group("independent_increments");
+    group.bench_function("plain", |b| {
+        let slots: [AtomicU64; SLOTS] = [const { AtomicU64::new(0) }; SLOTS];
+        b.iter_custom(|iters| run(threads, iters, |id| {
+            slots[id].fetch_add(1, Ordering::Relaxed);
+        }))
+    });
+    group.bench_function("padded", |b| {
+        let slots: [PaddedAtomic<AtomicU64>; SLOTS] = [const { PaddedAtomic::<AtomicU64>::new(0) }; SLOTS];
+        b.iter_custom(|iters| run(threads, iters, |id| {
+            slots[id].fetch_add(1, Ordering::Relaxed);
+        }))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, false_sharing);
+criterion_main!(benches);
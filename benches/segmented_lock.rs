@@ -0,0 +1,90 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::UnsafeCell,
+    collections::HashMap,
+    thread,
+    time::{Duration, Instant},
+};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use omango_sync::{mutex::Mutex, segmented_lock::SegmentedLock};
+
+const THREADS: u64 = 8;
+const KEYS: u64 = 4096;
+const SEGMENTS: usize = 64;
+
+/// A hash map split in segments, each guarded by the segment lock of its keys.
+struct SegmentedMap {
+    lock: SegmentedLock<SEGMENTS>,
+    maps: Vec<UnsafeCell<HashMap<u64, u64>>>,
+}
+
+unsafe impl Sync for SegmentedMap {}
+
+impl SegmentedMap {
+    fn new() -> Self {
+        Self {
+            lock: SegmentedLock::new(),
+            maps: (0..SEGMENTS).map(|_| UnsafeCell::new(HashMap::new())).collect(),
+        }
+    }
+
+    fn increment(&self, key: u64) {
+        let _guard = self.lock.lock_for(&key);
+        // The segment lock of the key guards its map.
+        let map = unsafe { &mut *self.maps[self.lock.segment_index(&key)].get() };
+        *map.entry(key).or_insert(0) += 1;
+    }
+}
+
+/// Runs `iters` increments on every thread and returns the wall time.
+fn run<F: Fn(u64) + Sync>(iters: u64, increment: F) -> Duration {
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for id in 0..THREADS {
+            let increment = &increment;
+            scope.spawn(move || {
+                for i in 0..iters {
+                    increment((id * 7919 + i) % KEYS);
+                }
+            });
+        }
+    });
+    start.elapsed()
+}
+
+fn maps(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hash_map_increments");
+    group.bench_function("global_lock", |b| {
+        let map = Mutex::new(HashMap::new());
+        b.iter_custom(|iters| run(iters, |key| *map.lock().entry(key).or_insert(0u64) += 1))
+    });
+    group.bench_function("segmented_lock", |b| {
+        let map = SegmentedMap::new();
+        b.iter_custom(|iters| run(iters, |key| map.increment(key)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, maps);
+criterion_main!(benches);
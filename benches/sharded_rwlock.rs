@@ -0,0 +1,101 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    collections::HashMap,
+    thread,
+    time::{Duration, Instant},
+};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+
+use omango_sync::{rwlock::RwLock, sharded_rwlock::ShardedRwLock};
+
+const READERS: u64 = 8;
+const WRITERS: u64 = 2;
+const KEYS: u64 = 1024;
+
+/// Runs `iters` operations on every reader and writer thread and returns the wall time.
+fn run<R, W>(iters: u64, read: R, write: W) -> Duration
+where
+    R: Fn(u64) + Sync,
+    W: Fn(u64) + Sync,
+{
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for id in 0..READERS {
+            let read = &read;
+            scope.spawn(move || {
+                for i in 0..iters {
+                    read((id * 31 + i) % KEYS);
+                }
+            });
+        }
+        for id in 0..WRITERS {
+            let write = &write;
+            scope.spawn(move || {
+                for i in 0..iters {
+                    write((id * 17 + i) % KEYS);
+                }
+            });
+        }
+    });
+    start.elapsed()
+}
+
+fn single(c: &mut Criterion) {
+    let lock = RwLock::new((0..KEYS).map(|key| (key, key.to_string())).collect::<HashMap<_, _>>());
+    c.bench_function("single_rwlock_8r_2w", |b| {
+        b.iter_custom(|iters| {
+            run(
+                iters,
+                |key| {
+                    assert!(lock.read().get(&key).is_some());
+                },
+                |key| {
+                    lock.write().insert(key, key.to_string());
+                },
+            )
+        })
+    });
+}
+
+fn sharded(c: &mut Criterion) {
+    let lock: ShardedRwLock<HashMap<u64, String>, 64> = ShardedRwLock::new();
+    for key in 0..KEYS {
+        lock.write(key as usize).insert(key, key.to_string());
+    }
+    c.bench_function("sharded_rwlock_8r_2w", |b| {
+        b.iter_custom(|iters| {
+            run(
+                iters,
+                |key| {
+                    assert!(lock.read(key as usize).get(&key).is_some());
+                },
+                |key| {
+                    lock.write(key as usize).insert(key, key.to_string());
+                },
+            )
+        })
+    });
+}
+
+criterion_group!(benches, single, sharded);
+criterion_main!(benches);
@@ -0,0 +1,65 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use omango_sync::{barrier::Barrier, spin_barrier::SpinBarrier};
+
+/// Runs `iters` rendezvous on `threads` threads and returns the wall time.
+fn run<W: Fn() + Sync>(threads: u32, iters: u64, wait: W) -> Duration {
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            scope.spawn(|| {
+                for _ in 0..iters {
+                    wait();
+                }
+            });
+        }
+    });
+    start.elapsed()
+}
+
+fn barriers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("barrier_rendezvous");
+    for threads in [2, 4, 8] {
+        group.bench_with_input(BenchmarkId::new("spin", threads), &threads, |b, &threads| {
+            let barrier = SpinBarrier::new(threads);
+            b.iter_custom(|iters| run(threads, iters, || {
+                barrier.wait();
+            }))
+        });
+        group.bench_with_input(BenchmarkId::new("futex", threads), &threads, |b, &threads| {
+            let barrier = Barrier::new(threads);
+            b.iter_custom(|iters| run(threads, iters, || {
+                barrier.wait();
+            }))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, barriers);
+criterion_main!(benches);
@@ -0,0 +1,92 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+use std::{
+    hint::black_box,
+    sync::atomic::{AtomicU64, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+use omango_sync::{rwlock::RwLock, stamped_lock::StampedLock};
+
+/// Runs `read` `iters` times on every thread and returns the wall time.
+fn run<R: Fn() -> u64 + Sync>(threads: usize, iters: u64, read: R) -> Duration {
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            let read = &read;
+            scope.spawn(move || {
+                for _ in 0..iters {
+                    black_box(read());
+                }
+            });
+        }
+    });
+    start.elapsed()
+}
+
+fn uncontended(c: &mut Criterion) {
+    let mut group = c.benchmark_group("uncontended_read");
+
+    let stamped = StampedLock::new();
+    let value = AtomicU64::new(1);
+    group.bench_function("optimistic_read", |b| {
+        b.iter(|| {
+            let stamp = stamped.optimistic_read();
+            let read = value.load(Ordering::Relaxed);
+            assert!(stamped.validate(stamp));
+            black_box(read)
+        })
+    });
+    group.bench_function("stamped_read_lock", |b| {
+        b.iter(|| {
+            let _guard = stamped.read_lock();
+            black_box(value.load(Ordering::Relaxed))
+        })
+    });
+
+    let rwlock = RwLock::new(1u64);
+    group.bench_function("rwlock_read", |b| b.iter(|| black_box(*rwlock.read())));
+    group.finish();
+}
+
+fn concurrent_readers(c: &mut Criterion) {
+    let mut group = c.benchmark_group("concurrent_read");
+    for threads in [1, 2, 4, 8] {
+        let stamped = StampedLock::new();
+        let value = AtomicU64::new(1);
+        group.bench_with_input(BenchmarkId::new("optimistic_read", threads), &threads, |b, &threads| {
+            b.iter_custom(|iters| run(threads, iters, || {
+                stamped.read(|| value.load(Ordering::Relaxed))
+            }))
+        });
+
+        let rwlock = RwLock::new(1u64);
+        group.bench_with_input(BenchmarkId::new("rwlock_read", threads), &threads, |b, &threads| {
+            b.iter_custom(|iters| run(threads, iters, || *rwlock.read()))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, uncontended, concurrent_readers);
+criterion_main!(benches);
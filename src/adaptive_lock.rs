@@ -0,0 +1,219 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::UnsafeCell,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use omango_util::hint::likely;
+
+const UNLOCKED: u32 = 0;
+const LOCKED: u32 = 1;
+const LOCKED_WAITERS: u32 = 2;
+
+// The number of recent lock-hold durations which drive the spin limit.
+const HISTORY: usize = 8;
+
+// Holds shorter than this make spinning worth it, longer ones make parking cheaper.
+const SHORT_HOLD_NANOS: u64 = 2_000;
+const LONG_HOLD_NANOS: u64 = 50_000;
+
+const MIN_SPIN: u32 = 1;
+const MAX_SPIN: u32 = 1 << 14;
+
+/// A mutual exclusion lock which spins for a while before parking on the futex.
+///
+/// The number of spins adapts to the recent lock-hold durations: it grows
+/// while the critical sections are short and shrinks while they are long.
+pub struct AdaptiveLock<T> {
+    state: AtomicU32,
+    spin_limit: AtomicU32,
+    holds: [AtomicU64; HISTORY],
+    next_hold: AtomicUsize,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send> Send for AdaptiveLock<T> {}
+
+unsafe impl<T: Send> Sync for AdaptiveLock<T> {}
+
+impl<T> AdaptiveLock<T> {
+    #[inline(always)]
+    pub fn new(value: T, spin_limit: u32) -> Self {
+        Self {
+            state: AtomicU32::new(UNLOCKED),
+            spin_limit: AtomicU32::new(spin_limit.clamp(MIN_SPIN, MAX_SPIN)),
+            holds: [const { AtomicU64::new(0) }; HISTORY],
+            next_hold: AtomicUsize::new(0),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Acquires the lock, spins then blocks the current thread until it is able to do so.
+    #[inline]
+    pub fn lock(&self) -> AdaptiveLockGuard<'_, T> {
+        if !likely(self.raw_try_lock()) {
+            self.lock_contended();
+        }
+        AdaptiveLockGuard {
+            parent: self,
+            acquired: Instant::now(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Acquires the lock without blocking.
+    #[inline]
+    pub fn try_lock(&self) -> Option<AdaptiveLockGuard<'_, T>> {
+        if self.raw_try_lock() {
+            return Some(AdaptiveLockGuard {
+                parent: self,
+                acquired: Instant::now(),
+                _marker: PhantomData,
+            });
+        }
+        None
+    }
+
+    /// Returns the current number of spins before parking.
+    #[inline(always)]
+    pub fn spin_limit(&self) -> u32 {
+        self.spin_limit.load(Ordering::Relaxed)
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+
+    #[inline(always)]
+    fn raw_try_lock(&self) -> bool {
+        self.state.compare_exchange(
+            UNLOCKED,
+            LOCKED,
+            Ordering::Acquire,
+            Ordering::Relaxed,
+        ).is_ok()
+    }
+
+    #[cold]
+    fn lock_contended(&self) {
+        // Spins on the CPU only, the futex is the way to give it up.
+        for _ in 0..self.adjust_spin_limit() {
+            if self.state.load(Ordering::Relaxed) == UNLOCKED && self.raw_try_lock() {
+                return;
+            }
+            std::hint::spin_loop();
+        }
+
+        // Marks there are waiters, so the owner will wake one up on unlock.
+        while self.state.swap(LOCKED_WAITERS, Ordering::Acquire) != UNLOCKED {
+            omango_futex::wait(&self.state, LOCKED_WAITERS);
+        }
+    }
+
+    /// Tunes the spin limit from the average of the recorded hold durations.
+    ///
+    /// The history and the limit are only hints, so the races between
+    /// the contending threads are harmless and relaxed operations are enough.
+    fn adjust_spin_limit(&self) -> u32 {
+        let (sum, count) = self.holds
+            .iter()
+            .map(|hold| hold.load(Ordering::Relaxed))
+            .filter(|&nanos| nanos > 0)
+            .fold((0u64, 0u64), |(sum, count), nanos| (sum.saturating_add(nanos), count + 1));
+
+        let limit = self.spin_limit.load(Ordering::Relaxed);
+        if count == 0 {
+            return limit;
+        }
+        let average = sum / count;
+        let new_limit = if average < SHORT_HOLD_NANOS {
+            limit.saturating_mul(2).min(MAX_SPIN)
+        } else if average > LONG_HOLD_NANOS {
+            (limit / 2).max(MIN_SPIN)
+        } else {
+            limit
+        };
+        if new_limit != limit {
+            self.spin_limit.store(new_limit, Ordering::Relaxed);
+        }
+        new_limit
+    }
+
+    #[inline]
+    fn record_hold(&self, acquired: Instant) {
+        let nanos = acquired.elapsed().as_nanos().min(u64::MAX as u128) as u64;
+        let index = self.next_hold.fetch_add(1, Ordering::Relaxed) % HISTORY;
+        self.holds[index].store(nanos.max(1), Ordering::Relaxed);
+    }
+}
+
+impl<T: Default> Default for AdaptiveLock<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(T::default(), 100)
+    }
+}
+
+/// Releases the [`AdaptiveLock`] on drop and records how long it was held.
+pub struct AdaptiveLockGuard<'a, T> {
+    parent: &'a AdaptiveLock<T>,
+    acquired: Instant,
+    // The guard must be dropped by the thread which locked the lock.
+    _marker: PhantomData<*const ()>,
+}
+
+unsafe impl<T: Sync> Sync for AdaptiveLockGuard<'_, T> {}
+
+impl<T> Drop for AdaptiveLockGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.parent.record_hold(self.acquired);
+        if self.parent.state.swap(UNLOCKED, Ordering::Release) == LOCKED_WAITERS {
+            omango_futex::wake_one(&self.parent.state);
+        }
+    }
+}
+
+impl<T> Deref for AdaptiveLockGuard<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        unsafe { &*self.parent.value.get() }
+    }
+}
+
+impl<T> DerefMut for AdaptiveLockGuard<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.parent.value.get() }
+    }
+}
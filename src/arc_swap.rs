@@ -0,0 +1,159 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    marker::PhantomData,
+    ops::Deref,
+    ptr,
+    sync::{Arc, atomic::Ordering},
+};
+
+use crate::{backoff::Backoff, hazard::HazardDomain, shim::AtomicPtr};
+
+/// An atomic slot holding an [`Arc`], which can be loaded and replaced concurrently.
+///
+/// Loading protects the pointer with a hazard pointer until its reference count
+/// is incremented, and the replaced pointer is only handed back to its owner once
+/// no loader protects it anymore, so its count never drops to zero under a loader.
+pub struct ArcSwap<T> {
+    ptr: AtomicPtr<T>,
+    // Its own domain, so a replacement only waits for the loaders of this instance.
+    hazard: HazardDomain,
+}
+
+unsafe impl<T: Send + Sync> Send for ArcSwap<T> {}
+
+unsafe impl<T: Send + Sync> Sync for ArcSwap<T> {}
+
+impl<T> ArcSwap<T> {
+    #[inline(always)]
+    pub fn new(value: Arc<T>) -> Self {
+        Self {
+            ptr: AtomicPtr::new(Arc::into_raw(value) as *mut T),
+            hazard: HazardDomain::new(),
+        }
+    }
+
+    #[inline(always)]
+    pub fn from_pointee(value: T) -> Self {
+        Self::new(Arc::new(value))
+    }
+
+    /// Returns a guard which keeps the current value alive.
+    pub fn load(&self) -> Guard<'_, T> {
+        let record = self.hazard.protect_load(&self.ptr);
+        let raw = record.as_ptr();
+        // The protection keeps the value alive until the guard has its own reference.
+        let arc = unsafe {
+            Arc::increment_strong_count(raw);
+            Arc::from_raw(raw)
+        };
+        drop(record);
+        Guard {
+            arc,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns a clone of the current value.
+    #[inline]
+    pub fn load_full(&self) -> Arc<T> {
+        self.load().into_arc()
+    }
+
+    /// Replaces the current value, the old one is released.
+    #[inline]
+    pub fn store(&self, new: Arc<T>) {
+        drop(self.swap(new));
+    }
+
+    /// Replaces the current value and returns the old one.
+    pub fn swap(&self, new: Arc<T>) -> Arc<T> {
+        let old = self.ptr.swap(Arc::into_raw(new) as *mut T, Ordering::AcqRel);
+        unsafe { self.release(old) }
+    }
+
+    /// Replaces the value with `new` only if it is still `current`.
+    ///
+    /// Returns the old value on success, otherwise `new` is given back.
+    pub fn compare_and_swap(&self, current: &Arc<T>, new: Arc<T>) -> Result<Arc<T>, Arc<T>> {
+        let new_raw = Arc::into_raw(new) as *mut T;
+        match self.ptr.compare_exchange(
+            Arc::as_ptr(current) as *mut T,
+            new_raw,
+            Ordering::AcqRel,
+            Ordering::Acquire,
+        ) {
+            Ok(old) => Ok(unsafe { self.release(old) }),
+            Err(_) => Err(unsafe { Arc::from_raw(new_raw) }),
+        }
+    }
+
+    /// Takes over the reference of a replaced pointer,
+    /// waits for the loaders which still protect it to take their own reference.
+    unsafe fn release(&self, old: *mut T) -> Arc<T> {
+        let mut backoff = Backoff::new();
+        while self.hazard.is_protected(old) {
+            backoff.snooze();
+        }
+        unsafe { Arc::from_raw(old) }
+    }
+}
+
+impl<T> Drop for ArcSwap<T> {
+    #[inline]
+    fn drop(&mut self) {
+        drop(unsafe { Arc::from_raw(self.ptr.load(Ordering::Relaxed)) });
+    }
+}
+
+impl<T: Default> Default for ArcSwap<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::from_pointee(T::default())
+    }
+}
+
+/// Keeps the value loaded from an [`ArcSwap`] alive.
+pub struct Guard<'a, T> {
+    arc: Arc<T>,
+    _marker: PhantomData<&'a ArcSwap<T>>,
+}
+
+impl<T> Guard<'_, T> {
+    #[inline(always)]
+    pub fn into_arc(self) -> Arc<T> {
+        self.arc
+    }
+
+    #[inline(always)]
+    pub fn ptr_eq(&self, other: &Arc<T>) -> bool {
+        ptr::eq(Arc::as_ptr(&self.arc), Arc::as_ptr(other))
+    }
+}
+
+impl<T> Deref for Guard<'_, T> {
+    type Target = Arc<T>;
+
+    #[inline(always)]
+    fn deref(&self) -> &Arc<T> {
+        &self.arc
+    }
+}
@@ -0,0 +1,134 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicU32, Ordering},
+    task::{Context, Poll},
+};
+
+use omango_util::lock::RwSpinlock;
+
+use crate::{async_semaphore::WakerQueue, barrier::BarrierWaitResult};
+
+/// A reusable barrier makes `n` tasks rendezvous before any of them proceeds.
+///
+/// The tasks are suspended instead of blocking the thread, the last arriving task
+/// of each cycle wakes up all the others.
+pub struct AsyncBarrier {
+    n: u32,
+    count: AtomicU32,
+    generation: AtomicU32,
+    wakers: RwSpinlock<WakerQueue>,
+}
+
+impl AsyncBarrier {
+    #[inline(always)]
+    pub fn new(n: u32) -> Self {
+        Self {
+            n,
+            count: AtomicU32::new(0),
+            generation: AtomicU32::new(0),
+            wakers: RwSpinlock::new(WakerQueue::new()),
+        }
+    }
+
+    /// Returns a future which resolves when all `n` tasks have called `wait`.
+    ///
+    /// Exactly one task of each cycle receives a leader result.
+    /// Dropping the future after its first poll does not withdraw the arrival.
+    #[inline(always)]
+    pub fn wait(&self) -> Wait<'_> {
+        Wait {
+            parent: self,
+            generation: None,
+            key: None,
+        }
+    }
+
+    /// Counts the arrival, returns `None` if the current task is the last one.
+    fn arrive(&self) -> Option<u32> {
+        // The arrival is counted under the queue lock, so that a task of the next cycle
+        // can not read the generation before the last task of this cycle has bumped it.
+        let mut wakers = self.wakers.write();
+        let generation = self.generation.load(Ordering::Relaxed);
+        if self.count.load(Ordering::Relaxed) + 1 < self.n {
+            self.count.fetch_add(1, Ordering::Relaxed);
+            return Some(generation);
+        }
+
+        self.count.store(0, Ordering::Relaxed);
+        self.generation.store(generation.wrapping_add(1), Ordering::Release);
+        let released = wakers.take_all();
+        drop(wakers);
+        for waker in released {
+            waker.wake();
+        }
+        None
+    }
+}
+
+/// The future returned by [`AsyncBarrier::wait`].
+pub struct Wait<'a> {
+    parent: &'a AsyncBarrier,
+    generation: Option<u32>,
+    key: Option<u64>,
+}
+
+impl Future for Wait<'_> {
+    type Output = BarrierWaitResult;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let parent = self.parent;
+        // Barrier of zero or one party never suspends.
+        if parent.n <= 1 {
+            return Poll::Ready(BarrierWaitResult(true));
+        }
+
+        let generation = match self.generation {
+            Some(generation) => generation,
+            None => match parent.arrive() {
+                Some(generation) => {
+                    self.generation = Some(generation);
+                    generation
+                }
+                None => return Poll::Ready(BarrierWaitResult(true)),
+            },
+        };
+
+        // The generation is checked again under the lock, the wakeup may be spurious.
+        let mut wakers = parent.wakers.write();
+        if parent.generation.load(Ordering::Acquire) != generation {
+            self.key = None;
+            return Poll::Ready(BarrierWaitResult(false));
+        }
+        wakers.register(&mut self.key, cx.waker());
+        Poll::Pending
+    }
+}
+
+impl Drop for Wait<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.parent.wakers.write().remove(key);
+        }
+    }
+}
@@ -0,0 +1,334 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::UnsafeCell,
+    future::Future,
+    mem::MaybeUninit,
+    pin::Pin,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    task::{Context, Poll},
+};
+
+use omango_util::lock::RwSpinlock;
+
+use crate::async_semaphore::WakerQueue;
+
+/// Creates a bounded multi-producer single-consumer channel for async tasks.
+///
+/// The channel holds at most `capacity` items, the sending tasks are suspended
+/// while it is full and the receiving task is suspended while it is empty.
+pub fn async_bounded<T: Send>(capacity: usize) -> (AsyncSender<T>, AsyncReceiver<T>) {
+    assert!(capacity > 0, "capacity must be greater than zero");
+
+    let buffer = (0..capacity)
+        .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+        .collect();
+    let shared = Arc::new(Shared {
+        buffer,
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+        send_lock: RwSpinlock::new(()),
+        senders: AtomicUsize::new(1),
+        disconnected: AtomicBool::new(false),
+        send_wakers: RwSpinlock::new(WakerQueue::new()),
+        recv_wakers: RwSpinlock::new(WakerQueue::new()),
+    });
+    let receiver = AsyncReceiver {
+        shared: shared.clone(),
+    };
+    (AsyncSender { shared }, receiver)
+}
+
+struct Shared<T> {
+    buffer: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    // Serializes the producers, the single consumer never takes it.
+    send_lock: RwSpinlock<()>,
+    senders: AtomicUsize,
+    disconnected: AtomicBool,
+    // The tasks waiting for a free slot and for an item.
+    send_wakers: RwSpinlock<WakerQueue>,
+    recv_wakers: RwSpinlock<WakerQueue>,
+}
+
+unsafe impl<T: Send> Send for Shared<T> {}
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+impl<T> Shared<T> {
+    #[inline(always)]
+    fn slot(&self, pos: usize) -> *mut MaybeUninit<T> {
+        self.buffer[pos % self.buffer.len()].get()
+    }
+
+    #[inline(always)]
+    fn has_space(&self) -> bool {
+        let head = self.head.load(Ordering::Acquire);
+        self.tail.load(Ordering::Acquire).wrapping_sub(head) < self.buffer.len()
+    }
+
+    #[inline(always)]
+    fn is_empty(&self) -> bool {
+        self.head.load(Ordering::Acquire) == self.tail.load(Ordering::Acquire)
+    }
+
+    #[inline]
+    fn wake_one(wakers: &RwSpinlock<WakerQueue>) {
+        let waker = wakers.write().pop();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+
+    #[inline]
+    fn wake_all(wakers: &RwSpinlock<WakerQueue>) {
+        let released = wakers.write().take_all();
+        for waker in released {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Drop for Shared<T> {
+    fn drop(&mut self) {
+        let tail = *self.tail.get_mut();
+        let mut head = *self.head.get_mut();
+        while head != tail {
+            unsafe { (*self.slot(head)).assume_init_drop() };
+            head = head.wrapping_add(1);
+        }
+    }
+}
+
+/// The sending side of the bounded async channel.
+pub struct AsyncSender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T: Send> AsyncSender<T> {
+    /// Returns a future which sends the value, it suspends the task while the channel is full.
+    ///
+    /// The future resolves to the value back if the receiver was dropped.
+    #[inline(always)]
+    pub fn send(&self, value: T) -> SendFuture<'_, T> {
+        SendFuture {
+            parent: self,
+            value: Some(value),
+            key: None,
+        }
+    }
+
+    /// Sends a value without suspending.
+    ///
+    /// Returns the value back if the channel is full or the receiver was dropped.
+    pub fn try_send(&self, value: T) -> Result<(), T> {
+        let shared = &self.shared;
+        if shared.disconnected.load(Ordering::Acquire) {
+            return Err(value);
+        }
+        {
+            let _guard = shared.send_lock.write();
+            let tail = shared.tail.load(Ordering::Relaxed);
+            let head = shared.head.load(Ordering::Acquire);
+            if tail.wrapping_sub(head) == shared.buffer.len() {
+                return Err(value);
+            }
+            unsafe { (*shared.slot(tail)).write(value) };
+            shared.tail.store(tail.wrapping_add(1), Ordering::Release);
+        }
+        Shared::<T>::wake_one(&shared.recv_wakers);
+        Ok(())
+    }
+}
+
+impl<T> Clone for AsyncSender<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.shared.senders.fetch_add(1, Ordering::Relaxed);
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for AsyncSender<T> {
+    fn drop(&mut self) {
+        if self.shared.senders.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.shared.disconnected.store(true, Ordering::Release);
+            Shared::<T>::wake_all(&self.shared.recv_wakers);
+        }
+    }
+}
+
+/// The future returned by [`AsyncSender::send`].
+pub struct SendFuture<'a, T> {
+    parent: &'a AsyncSender<T>,
+    value: Option<T>,
+    key: Option<u64>,
+}
+
+// The value is moved in and out by value, it is never pinned.
+impl<T> Unpin for SendFuture<'_, T> {}
+
+impl<T: Send> Future for SendFuture<'_, T> {
+    type Output = Result<(), T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let shared = &self.parent.shared;
+        loop {
+            let value = self.value.take().expect("send future polled after completion");
+            let value = match self.parent.try_send(value) {
+                Ok(()) => return Poll::Ready(self.complete(Ok(()))),
+                Err(value) if shared.disconnected.load(Ordering::Acquire) => {
+                    return Poll::Ready(self.complete(Err(value)));
+                }
+                Err(value) => value,
+            };
+            self.value = Some(value);
+
+            // The receiver frees a slot before popping the waker, so the channel is checked
+            // again under the queue lock. Sending is retried outside of it, since it wakes
+            // the receiver under the other queue lock.
+            let mut wakers = shared.send_wakers.write();
+            if shared.has_space() || shared.disconnected.load(Ordering::Acquire) {
+                continue;
+            }
+            wakers.register(&mut self.key, cx.waker());
+            return Poll::Pending;
+        }
+    }
+}
+
+impl<T> SendFuture<'_, T> {
+    #[inline]
+    fn complete(&mut self, result: Result<(), T>) -> Result<(), T> {
+        if let Some(key) = self.key.take() {
+            self.parent.shared.send_wakers.write().remove(key);
+        }
+        result
+    }
+}
+
+impl<T> Drop for SendFuture<'_, T> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            let removed = self.parent.shared.send_wakers.write().remove(key);
+            if !removed {
+                // The task was woken for a free slot but gave up, so the wakeup
+                // is handed over to the next queued task.
+                Shared::<T>::wake_one(&self.parent.shared.send_wakers);
+            }
+        }
+    }
+}
+
+/// The receiving side of the bounded async channel.
+///
+/// Receiving takes `&mut self`, so there is a single receiving task at a time.
+pub struct AsyncReceiver<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T: Send> AsyncReceiver<T> {
+    /// Returns a future which receives a value, it suspends the task while the channel is empty.
+    ///
+    /// The future resolves to `None` once all senders were dropped and the remaining items are drained.
+    #[inline(always)]
+    pub fn recv(&mut self) -> RecvFuture<'_, T> {
+        RecvFuture { parent: self, key: None }
+    }
+
+    /// Receives a value without suspending, returns `None` if the channel is empty.
+    pub fn try_recv(&mut self) -> Option<T> {
+        let shared = &self.shared;
+        let head = shared.head.load(Ordering::Relaxed);
+        if head == shared.tail.load(Ordering::Acquire) {
+            return None;
+        }
+        let value = unsafe { (*shared.slot(head)).assume_init_read() };
+        shared.head.store(head.wrapping_add(1), Ordering::Release);
+        Shared::<T>::wake_one(&shared.send_wakers);
+        Some(value)
+    }
+}
+
+impl<T> Drop for AsyncReceiver<T> {
+    fn drop(&mut self) {
+        self.shared.disconnected.store(true, Ordering::Release);
+        Shared::<T>::wake_all(&self.shared.send_wakers);
+    }
+}
+
+/// The future returned by [`AsyncReceiver::recv`].
+pub struct RecvFuture<'a, T> {
+    parent: &'a mut AsyncReceiver<T>,
+    key: Option<u64>,
+}
+
+impl<T: Send> Future for RecvFuture<'_, T> {
+    type Output = Option<T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = &mut *self;
+        loop {
+            if let Some(value) = this.parent.try_recv() {
+                return Poll::Ready(this.complete(Some(value)));
+            }
+            let shared = &this.parent.shared;
+            if shared.disconnected.load(Ordering::Acquire) {
+                // A sender may have pushed right before the last one was dropped.
+                let value = this.parent.try_recv();
+                return Poll::Ready(this.complete(value));
+            }
+
+            // The senders push the item before popping the waker, so the channel is checked
+            // again under the queue lock. Receiving is retried outside of it, since it wakes
+            // the senders under the other queue lock.
+            let mut wakers = shared.recv_wakers.write();
+            if !shared.is_empty() || shared.disconnected.load(Ordering::Acquire) {
+                continue;
+            }
+            wakers.register(&mut this.key, cx.waker());
+            return Poll::Pending;
+        }
+    }
+}
+
+impl<T> RecvFuture<'_, T> {
+    #[inline]
+    fn complete(&mut self, result: Option<T>) -> Option<T> {
+        if let Some(key) = self.key.take() {
+            self.parent.shared.recv_wakers.write().remove(key);
+        }
+        result
+    }
+}
+
+impl<T> Drop for RecvFuture<'_, T> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.parent.shared.recv_wakers.write().remove(key);
+        }
+    }
+}
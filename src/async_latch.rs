@@ -0,0 +1,141 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicI32, AtomicU32, Ordering},
+    task::{Context, Poll},
+};
+
+use omango_util::lock::RwSpinlock;
+
+use crate::async_semaphore::WakerQueue;
+
+/// A latch suspends tasks until its count reaches zero.
+///
+/// It is the async counterpart of [`CountdownLatch`]: once the count reaches zero
+/// the latch stays open and all later waits complete immediately.
+/// Counting down is synchronous, so it can be called from threads as well as tasks.
+///
+/// [`CountdownLatch`]: crate::latch::CountdownLatch
+pub struct AsyncCountdownLatch {
+    count: AtomicI32,
+    open: AtomicU32,
+    wakers: RwSpinlock<WakerQueue>,
+}
+
+impl AsyncCountdownLatch {
+    #[inline(always)]
+    pub fn new(n: u32) -> Self {
+        Self {
+            count: AtomicI32::new(n as i32),
+            open: AtomicU32::new((n == 0) as u32),
+            wakers: RwSpinlock::new(WakerQueue::new()),
+        }
+    }
+
+    /// Decrements the count and wakes up all waiting tasks when it reaches zero.
+    ///
+    /// Counting down an opened latch is a no-op.
+    pub fn count_down(&self) {
+        let mut count = self.count.load(Ordering::Relaxed);
+        while count > 0 {
+            match self.count.compare_exchange_weak(
+                count,
+                count - 1,
+                Ordering::AcqRel,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    if count == 1 {
+                        self.open_latch();
+                    }
+                    return;
+                }
+                Err(current) => count = current,
+            }
+        }
+    }
+
+    /// Returns a future which resolves when the latch is opened.
+    #[inline(always)]
+    pub fn await_zero(&self) -> AwaitZero<'_> {
+        AwaitZero { parent: self, key: None }
+    }
+
+    #[inline(always)]
+    pub fn count(&self) -> u32 {
+        self.count.load(Ordering::Relaxed) as u32
+    }
+
+    #[inline(always)]
+    pub fn is_open(&self) -> bool {
+        self.open.load(Ordering::Acquire) == 1
+    }
+
+    fn open_latch(&self) {
+        // The flag is set under the queue lock, so a task either sees it open
+        // or has queued its waker before the wakers are taken.
+        let released = {
+            let mut wakers = self.wakers.write();
+            self.open.store(1, Ordering::Release);
+            wakers.take_all()
+        };
+        for waker in released {
+            waker.wake();
+        }
+    }
+}
+
+/// The future returned by [`AsyncCountdownLatch::await_zero`].
+pub struct AwaitZero<'a> {
+    parent: &'a AsyncCountdownLatch,
+    key: Option<u64>,
+}
+
+impl Future for AwaitZero<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let parent = self.parent;
+        if parent.is_open() {
+            return Poll::Ready(());
+        }
+
+        let mut wakers = parent.wakers.write();
+        if parent.is_open() {
+            return Poll::Ready(());
+        }
+        wakers.register(&mut self.key, cx.waker());
+        Poll::Pending
+    }
+}
+
+impl Drop for AwaitZero<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            // The queue is emptied when the latch opens, there is nothing to remove then.
+            if !self.parent.is_open() {
+                self.parent.wakers.write().remove(key);
+            }
+        }
+    }
+}
@@ -0,0 +1,205 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::UnsafeCell,
+    future::Future,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    sync::atomic::{AtomicBool, Ordering},
+    task::{Context, Poll},
+};
+
+use omango_util::lock::RwSpinlock;
+
+use crate::async_semaphore::WakerQueue;
+
+/// A mutual exclusion lock for async tasks.
+///
+/// A task which finds the lock taken is suspended instead of blocking the thread,
+/// its waker is queued and woken in FIFO order when the lock is released.
+/// The guard can be held across `.await` points.
+pub struct AsyncMutex<T> {
+    locked: AtomicBool,
+    wakers: RwSpinlock<WakerQueue>,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send> Send for AsyncMutex<T> {}
+
+unsafe impl<T: Send> Sync for AsyncMutex<T> {}
+
+impl<T> AsyncMutex<T> {
+    #[inline(always)]
+    pub fn new(value: T) -> Self {
+        Self {
+            locked: AtomicBool::new(false),
+            wakers: RwSpinlock::new(WakerQueue::new()),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Returns a future which acquires the lock.
+    #[inline(always)]
+    pub fn lock(&self) -> Lock<'_, T> {
+        Lock { parent: self, key: None }
+    }
+
+    /// Acquires the lock without suspending.
+    #[inline]
+    pub fn try_lock(&self) -> Option<AsyncMutexGuard<'_, T>> {
+        if self.locked.compare_exchange(
+            false,
+            true,
+            Ordering::Acquire,
+            Ordering::Relaxed,
+        ).is_ok() {
+            return Some(AsyncMutexGuard {
+                parent: self,
+                _marker: PhantomData,
+            });
+        }
+        None
+    }
+
+    #[inline(always)]
+    pub fn is_locked(&self) -> bool {
+        self.locked.load(Ordering::Relaxed)
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+
+    #[inline]
+    fn unlock(&self) {
+        // The lock must be released before popping the waker, a task which queues itself
+        // concurrently tries the lock again while holding the queue lock.
+        self.locked.store(false, Ordering::Release);
+        self.wake_one();
+    }
+
+    #[inline]
+    fn wake_one(&self) {
+        let waker = self.wakers.write().pop();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl<T: Default> Default for AsyncMutex<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+/// The future returned by [`AsyncMutex::lock`].
+pub struct Lock<'a, T> {
+    parent: &'a AsyncMutex<T>,
+    key: Option<u64>,
+}
+
+impl<'a, T> Future for Lock<'a, T> {
+    type Output = AsyncMutexGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let parent = self.parent;
+        if let Some(guard) = parent.try_lock() {
+            self.complete();
+            return Poll::Ready(guard);
+        }
+
+        let mut wakers = parent.wakers.write();
+        if let Some(guard) = parent.try_lock() {
+            drop(wakers);
+            self.complete();
+            return Poll::Ready(guard);
+        }
+        wakers.register(&mut self.key, cx.waker());
+        Poll::Pending
+    }
+}
+
+impl<T> Lock<'_, T> {
+    #[inline]
+    fn complete(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.parent.wakers.write().remove(key);
+        }
+    }
+}
+
+impl<T> Drop for Lock<'_, T> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            let removed = self.parent.wakers.write().remove(key);
+            if !removed {
+                // The task was woken for the lock but gave up, so the wakeup
+                // is handed over to the next queued task.
+                self.parent.wake_one();
+            }
+        }
+    }
+}
+
+/// Releases the [`AsyncMutex`] on drop and wakes up the first queued task if any.
+pub struct AsyncMutexGuard<'a, T> {
+    parent: &'a AsyncMutex<T>,
+    // The auto traits would follow the mutex, they are implemented below from the data instead.
+    _marker: PhantomData<*const ()>,
+}
+
+// The guard can be released by any thread, so it can be held across `.await`
+// in a task which moves between the threads.
+unsafe impl<T: Send> Send for AsyncMutexGuard<'_, T> {}
+
+unsafe impl<T: Sync> Sync for AsyncMutexGuard<'_, T> {}
+
+impl<T> Drop for AsyncMutexGuard<'_, T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.parent.unlock();
+    }
+}
+
+impl<T> Deref for AsyncMutexGuard<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        unsafe { &*self.parent.value.get() }
+    }
+}
+
+impl<T> DerefMut for AsyncMutexGuard<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.parent.value.get() }
+    }
+}
@@ -0,0 +1,178 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicU32, Ordering},
+    task::{Context, Poll},
+};
+
+use omango_util::lock::RwSpinlock;
+
+use crate::async_semaphore::WakerQueue;
+
+/// Notifies tasks about an event, storing permits when nobody is waiting.
+///
+/// It is the async counterpart of [`Notify`]: a notification sent before `notified`
+/// is not lost, the next `notified` consumes the stored permit and completes immediately.
+/// Unlike [`Notify`], `notify_all` only wakes the tasks which are waiting at the time.
+///
+/// [`Notify`]: crate::notify::Notify
+pub struct AsyncNotify {
+    permits: AtomicU32,
+    // Bumped by each `notify_all`, so the released tasks do not need a permit.
+    generation: AtomicU32,
+    wakers: RwSpinlock<WakerQueue>,
+}
+
+impl AsyncNotify {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            permits: AtomicU32::new(0),
+            generation: AtomicU32::new(0),
+            wakers: RwSpinlock::new(WakerQueue::new()),
+        }
+    }
+
+    /// Stores one permit and wakes up the first waiting task.
+    #[inline]
+    pub fn notify_one(&self) {
+        let _ = self.permits.fetch_update(
+            Ordering::Release,
+            Ordering::Relaxed,
+            |permits| Some(permits.saturating_add(1)),
+        );
+        self.wake_one();
+    }
+
+    /// Wakes up all waiting tasks without storing a permit.
+    pub fn notify_all(&self) {
+        let released = {
+            let mut wakers = self.wakers.write();
+            self.generation.fetch_add(1, Ordering::Release);
+            wakers.take_all()
+        };
+        for waker in released {
+            waker.wake();
+        }
+    }
+
+    /// Returns a future which consumes one permit, or completes on the next `notify_all`.
+    #[inline(always)]
+    pub fn notified(&self) -> Notified<'_> {
+        Notified {
+            parent: self,
+            generation: None,
+            key: None,
+        }
+    }
+
+    /// Consumes one permit without suspending.
+    ///
+    /// Returns `false` if there is no stored permit.
+    #[inline]
+    pub fn try_notified(&self) -> bool {
+        self.permits.fetch_update(
+            Ordering::Acquire,
+            Ordering::Relaxed,
+            |permits| permits.checked_sub(1),
+        ).is_ok()
+    }
+
+    #[inline]
+    fn wake_one(&self) {
+        let waker = self.wakers.write().pop();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+impl Default for AsyncNotify {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The future returned by [`AsyncNotify::notified`].
+pub struct Notified<'a> {
+    parent: &'a AsyncNotify,
+    // The generation seen when the task was queued for the first time.
+    generation: Option<u32>,
+    key: Option<u64>,
+}
+
+impl Notified<'_> {
+    #[inline(always)]
+    fn is_released(&self) -> bool {
+        self.generation.is_some_and(|generation| {
+            self.parent.generation.load(Ordering::Acquire) != generation
+        })
+    }
+
+    #[inline]
+    fn complete(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.parent.wakers.write().remove(key);
+        }
+    }
+}
+
+impl Future for Notified<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let parent = self.parent;
+        if self.is_released() || parent.try_notified() {
+            self.complete();
+            return Poll::Ready(());
+        }
+
+        // The notifiers publish the permit or the generation before waking,
+        // so both are checked again under the queue lock.
+        let mut wakers = parent.wakers.write();
+        if self.is_released() || parent.try_notified() {
+            drop(wakers);
+            self.complete();
+            return Poll::Ready(());
+        }
+        if self.generation.is_none() {
+            self.generation = Some(parent.generation.load(Ordering::Relaxed));
+        }
+        wakers.register(&mut self.key, cx.waker());
+        Poll::Pending
+    }
+}
+
+impl Drop for Notified<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            let removed = self.parent.wakers.write().remove(key);
+            if !removed && !self.is_released() {
+                // The task was woken for a permit but gave up, so the wakeup
+                // is handed over to the next queued task.
+                self.parent.wake_one();
+            }
+        }
+    }
+}
@@ -0,0 +1,357 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::UnsafeCell,
+    future::Future,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    sync::atomic::{AtomicUsize, Ordering},
+    task::{Context, Poll, Waker},
+};
+
+use omango_util::lock::RwSpinlock;
+
+use crate::async_semaphore::WakerQueue;
+
+const WRITE_LOCKED: usize = 1 << (usize::BITS - 1);
+const WRITERS_WAITING: usize = 1 << (usize::BITS - 2);
+const READERS_MASK: usize = WRITERS_WAITING - 1;
+
+/// A reader-writer lock for async tasks.
+///
+/// The low bits of the state word count the readers, the two high bits mark
+/// the active writer and the queued writers.
+///
+/// New readers are queued as soon as a writer is waiting, so the writers are not
+/// starved under the heavy read load. The lock is handed over to the queued tasks
+/// directly: an unlocking writer admits all queued readers if any, otherwise
+/// the next queued writer, and the last leaving reader admits the next queued writer.
+pub struct AsyncRwLock<T> {
+    state: AtomicUsize,
+    queues: RwSpinlock<Queues>,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send> Send for AsyncRwLock<T> {}
+
+unsafe impl<T: Send + Sync> Sync for AsyncRwLock<T> {}
+
+struct Queues {
+    readers: WakerQueue,
+    writers: WakerQueue,
+}
+
+impl<T> AsyncRwLock<T> {
+    #[inline(always)]
+    pub fn new(value: T) -> Self {
+        Self {
+            state: AtomicUsize::new(0),
+            queues: RwSpinlock::new(Queues {
+                readers: WakerQueue::new(),
+                writers: WakerQueue::new(),
+            }),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Returns a future which acquires the shared access.
+    #[inline(always)]
+    pub fn read(&self) -> Read<'_, T> {
+        Read { parent: self, key: None }
+    }
+
+    /// Acquires the shared access without suspending.
+    pub fn try_read(&self) -> Option<AsyncReadGuard<'_, T>> {
+        let mut state = self.state.load(Ordering::Relaxed);
+        while is_read_lockable(state) {
+            match self.state.compare_exchange_weak(
+                state,
+                state + 1,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return Some(AsyncReadGuard { parent: self }),
+                Err(current) => state = current,
+            }
+        }
+        None
+    }
+
+    /// Returns a future which acquires the exclusive access.
+    #[inline(always)]
+    pub fn write(&self) -> Write<'_, T> {
+        Write { parent: self, key: None }
+    }
+
+    /// Acquires the exclusive access without suspending.
+    ///
+    /// It fails while any writer is queued, so the queued writers are not overtaken.
+    #[inline]
+    pub fn try_write(&self) -> Option<AsyncWriteGuard<'_, T>> {
+        if self.state.compare_exchange(
+            0,
+            WRITE_LOCKED,
+            Ordering::Acquire,
+            Ordering::Relaxed,
+        ).is_ok() {
+            return Some(AsyncWriteGuard { parent: self });
+        }
+        None
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+
+    fn read_unlock(&self) {
+        let state = self.state.fetch_sub(1, Ordering::AcqRel);
+        if state & READERS_MASK == 1 && state & WRITERS_WAITING != 0 {
+            let waker = {
+                let mut queues = self.queues.write();
+                self.admit_writer(&mut queues)
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+
+    fn write_unlock(&self) {
+        let released = {
+            let mut queues = self.queues.write();
+            if !queues.readers.is_empty() {
+                // No other task changes the state while it is write-locked.
+                let readers = queues.readers.len();
+                self.state.store(readers | writers_waiting(&queues), Ordering::Release);
+                queues.readers.take_all()
+            } else if let Some(waker) = queues.writers.pop() {
+                self.state.store(WRITE_LOCKED | writers_waiting(&queues), Ordering::Release);
+                vec![waker]
+            } else {
+                self.state.store(0, Ordering::Release);
+                Vec::new()
+            }
+        };
+        for waker in released {
+            waker.wake();
+        }
+    }
+
+    /// Hands the lock over to the first queued writer if it is free.
+    fn admit_writer(&self, queues: &mut Queues) -> Option<Waker> {
+        // The waiting bit keeps the new readers and writers out, the state can only
+        // be changed by the tasks holding the queue lock.
+        if self.state.load(Ordering::Acquire) & (WRITE_LOCKED | READERS_MASK) != 0 {
+            return None;
+        }
+        let waker = queues.writers.pop()?;
+        self.state.store(WRITE_LOCKED | writers_waiting(queues), Ordering::Release);
+        Some(waker)
+    }
+
+    /// Admits all queued readers after the last queued writer has left.
+    fn admit_readers(&self, queues: &mut Queues) -> Vec<Waker> {
+        if self.state.load(Ordering::Acquire) & WRITE_LOCKED != 0 {
+            return Vec::new();
+        }
+        self.state.fetch_add(queues.readers.len(), Ordering::Release);
+        queues.readers.take_all()
+    }
+}
+
+impl<T: Default> Default for AsyncRwLock<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+#[inline(always)]
+fn is_read_lockable(state: usize) -> bool {
+    state & (WRITE_LOCKED | WRITERS_WAITING) == 0 && state & READERS_MASK < READERS_MASK
+}
+
+#[inline(always)]
+fn writers_waiting(queues: &Queues) -> usize {
+    if queues.writers.is_empty() { 0 } else { WRITERS_WAITING }
+}
+
+/// The future returned by [`AsyncRwLock::read`].
+pub struct Read<'a, T> {
+    parent: &'a AsyncRwLock<T>,
+    key: Option<u64>,
+}
+
+impl<'a, T> Future for Read<'a, T> {
+    type Output = AsyncReadGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let parent = self.parent;
+        if self.key.is_none() {
+            if let Some(guard) = parent.try_read() {
+                return Poll::Ready(guard);
+            }
+        }
+
+        let mut queues = parent.queues.write();
+        if let Some(key) = self.key {
+            // The key leaves the queue only when the read access is handed over.
+            if !queues.readers.contains(key) {
+                self.key = None;
+                return Poll::Ready(AsyncReadGuard { parent });
+            }
+        } else if let Some(guard) = parent.try_read() {
+            return Poll::Ready(guard);
+        }
+        queues.readers.register(&mut self.key, cx.waker());
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for Read<'_, T> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            let removed = self.parent.queues.write().readers.remove(key);
+            if !removed {
+                // The read access was handed over, but the task gave up.
+                self.parent.read_unlock();
+            }
+        }
+    }
+}
+
+/// The future returned by [`AsyncRwLock::write`].
+pub struct Write<'a, T> {
+    parent: &'a AsyncRwLock<T>,
+    key: Option<u64>,
+}
+
+impl<'a, T> Future for Write<'a, T> {
+    type Output = AsyncWriteGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let parent = self.parent;
+        if self.key.is_none() {
+            if let Some(guard) = parent.try_write() {
+                return Poll::Ready(guard);
+            }
+        }
+
+        let mut queues = parent.queues.write();
+        if let Some(key) = self.key {
+            // The key leaves the queue only when the write access is handed over.
+            if !queues.writers.contains(key) {
+                self.key = None;
+                return Poll::Ready(AsyncWriteGuard { parent });
+            }
+        } else {
+            // The waiting bit is set before the waker is queued, so that the last leaving reader
+            // either sees it or has already left when the state is checked here.
+            let state = parent.state.fetch_or(WRITERS_WAITING, Ordering::AcqRel);
+            if state & (WRITE_LOCKED | READERS_MASK) == 0 {
+                parent.state.store(WRITE_LOCKED | writers_waiting(&queues), Ordering::Relaxed);
+                return Poll::Ready(AsyncWriteGuard { parent });
+            }
+        }
+        queues.writers.register(&mut self.key, cx.waker());
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for Write<'_, T> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            let parent = self.parent;
+            let mut queues = parent.queues.write();
+            if !queues.writers.remove(key) {
+                // The write access was handed over, but the task gave up.
+                drop(queues);
+                parent.write_unlock();
+                return;
+            }
+            if queues.writers.is_empty() {
+                // The readers were queued only because of the writers.
+                parent.state.fetch_and(!WRITERS_WAITING, Ordering::Release);
+                let released = parent.admit_readers(&mut queues);
+                drop(queues);
+                for waker in released {
+                    waker.wake();
+                }
+            }
+        }
+    }
+}
+
+/// Releases the shared access of the [`AsyncRwLock`] on drop.
+pub struct AsyncReadGuard<'a, T> {
+    parent: &'a AsyncRwLock<T>,
+}
+
+impl<T> Drop for AsyncReadGuard<'_, T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.parent.read_unlock();
+    }
+}
+
+impl<T> Deref for AsyncReadGuard<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        unsafe { &*self.parent.value.get() }
+    }
+}
+
+/// Releases the exclusive access of the [`AsyncRwLock`] on drop,
+/// handing it over to the queued tasks if any.
+pub struct AsyncWriteGuard<'a, T> {
+    parent: &'a AsyncRwLock<T>,
+}
+
+impl<T> Drop for AsyncWriteGuard<'_, T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.parent.write_unlock();
+    }
+}
+
+impl<T> Deref for AsyncWriteGuard<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        unsafe { &*self.parent.value.get() }
+    }
+}
+
+impl<T> DerefMut for AsyncWriteGuard<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.parent.value.get() }
+    }
+}
@@ -0,0 +1,233 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    sync::atomic::{AtomicU32, Ordering},
+    task::{Context, Poll, Waker},
+};
+
+use omango_util::lock::RwSpinlock;
+
+/// A counting semaphore for async tasks.
+///
+/// Tasks which can not get a permit are suspended instead of blocking the thread,
+/// their wakers are queued and woken in FIFO order when the permits are released.
+pub struct AsyncSemaphore {
+    permits: AtomicU32,
+    wakers: RwSpinlock<WakerQueue>,
+}
+
+impl AsyncSemaphore {
+    #[inline(always)]
+    pub fn new(permits: u32) -> Self {
+        Self {
+            permits: AtomicU32::new(permits),
+            wakers: RwSpinlock::new(WakerQueue::new()),
+        }
+    }
+
+    /// Returns a future which takes one permit, it resolves to a guard
+    /// which gives the permit back on drop.
+    #[inline(always)]
+    pub fn acquire(&self) -> Acquire<'_> {
+        Acquire { parent: self, key: None }
+    }
+
+    /// Takes one permit without suspending.
+    pub fn try_acquire(&self) -> Option<AsyncSemaphoreGuard<'_>> {
+        let mut permits = self.permits.load(Ordering::Relaxed);
+        while permits > 0 {
+            match self.permits.compare_exchange_weak(
+                permits,
+                permits - 1,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return Some(AsyncSemaphoreGuard { parent: self }),
+                Err(current) => permits = current,
+            }
+        }
+        None
+    }
+
+    #[inline(always)]
+    pub fn available_permits(&self) -> u32 {
+        self.permits.load(Ordering::Relaxed)
+    }
+
+    #[inline(always)]
+    pub fn has_waiters(&self) -> bool {
+        !self.wakers.read().is_empty()
+    }
+
+    /// Gives back one permit and wakes up the first queued task if any.
+    #[inline]
+    fn release(&self) {
+        // The permit must be visible before popping the waker, a task which queues itself
+        // concurrently checks the permits again while holding the queue lock.
+        self.permits.fetch_add(1, Ordering::Release);
+        self.wake_one();
+    }
+
+    #[inline]
+    fn wake_one(&self) {
+        let waker = self.wakers.write().pop();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// The future returned by [`AsyncSemaphore::acquire`].
+pub struct Acquire<'a> {
+    parent: &'a AsyncSemaphore,
+    key: Option<u64>,
+}
+
+impl<'a> Future for Acquire<'a> {
+    type Output = AsyncSemaphoreGuard<'a>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let parent = self.parent;
+        if let Some(guard) = parent.try_acquire() {
+            self.complete();
+            return Poll::Ready(guard);
+        }
+
+        let mut wakers = parent.wakers.write();
+        if let Some(guard) = parent.try_acquire() {
+            drop(wakers);
+            self.complete();
+            return Poll::Ready(guard);
+        }
+        wakers.register(&mut self.key, cx.waker());
+        Poll::Pending
+    }
+}
+
+impl Acquire<'_> {
+    #[inline]
+    fn complete(&mut self) {
+        if let Some(key) = self.key.take() {
+            self.parent.wakers.write().remove(key);
+        }
+    }
+}
+
+impl Drop for Acquire<'_> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            let removed = self.parent.wakers.write().remove(key);
+            if !removed {
+                // The task was woken for a permit but gave up, so the wakeup
+                // is handed over to the next queued task.
+                self.parent.wake_one();
+            }
+        }
+    }
+}
+
+/// Gives back the permit to the [`AsyncSemaphore`] on drop.
+pub struct AsyncSemaphoreGuard<'a> {
+    parent: &'a AsyncSemaphore,
+}
+
+impl Drop for AsyncSemaphoreGuard<'_> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.parent.release();
+    }
+}
+
+/// The FIFO queue of the suspended tasks, it must be protected by a lock.
+///
+/// Each registered task is identified by a key, so that it can update its waker
+/// when it is polled again and leave the queue when its future is dropped.
+pub(crate) struct WakerQueue {
+    wakers: VecDeque<(u64, Waker)>,
+    next_key: u64,
+}
+
+impl WakerQueue {
+    #[inline(always)]
+    pub(crate) const fn new() -> Self {
+        Self {
+            wakers: VecDeque::new(),
+            next_key: 0,
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.wakers.is_empty()
+    }
+
+    #[inline(always)]
+    pub(crate) fn len(&self) -> usize {
+        self.wakers.len()
+    }
+
+    #[inline]
+    pub(crate) fn contains(&self, key: u64) -> bool {
+        self.wakers.iter().any(|(k, _)| *k == key)
+    }
+
+    /// Queues the waker, or replaces the queued one if the task is still in the queue.
+    pub(crate) fn register(&mut self, key: &mut Option<u64>, waker: &Waker) {
+        if let Some(current) = *key {
+            if let Some((_, queued)) = self.wakers.iter_mut().find(|(k, _)| *k == current) {
+                if !queued.will_wake(waker) {
+                    queued.clone_from(waker);
+                }
+                return;
+            }
+        }
+        let new_key = self.next_key;
+        self.next_key = self.next_key.wrapping_add(1);
+        self.wakers.push_back((new_key, waker.clone()));
+        *key = Some(new_key);
+    }
+
+    /// Removes the task from the queue.
+    ///
+    /// Returns `false` if it was popped already.
+    pub(crate) fn remove(&mut self, key: u64) -> bool {
+        match self.wakers.iter().position(|(k, _)| *k == key) {
+            Some(idx) => {
+                self.wakers.remove(idx);
+                true
+            }
+            None => false,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn pop(&mut self) -> Option<Waker> {
+        self.wakers.pop_front().map(|(_, waker)| waker)
+    }
+
+    #[inline]
+    pub(crate) fn take_all(&mut self) -> Vec<Waker> {
+        self.wakers.drain(..).map(|(_, waker)| waker).collect()
+    }
+}
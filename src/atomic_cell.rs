@@ -0,0 +1,157 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::UnsafeCell,
+    mem::{self, ManuallyDrop},
+    sync::atomic::{AtomicU16, AtomicU32, AtomicU64, AtomicU8, Ordering},
+};
+
+use omango_util::lock::RwSpinlock;
+
+/// Runs `$body` with `$a` bound to the native atomic type which has the same size as `T`,
+/// and `$int` bound to its integer type.
+macro_rules! with_atomic {
+    ($size:expr, $ptr:expr, $a:ident, $int:ident => $body:expr) => {
+        match $size {
+            1 => {
+                type $int = u8;
+                let $a = &*($ptr as *const AtomicU8);
+                $body
+            }
+            2 => {
+                type $int = u16;
+                let $a = &*($ptr as *const AtomicU16);
+                $body
+            }
+            4 => {
+                type $int = u32;
+                let $a = &*($ptr as *const AtomicU32);
+                $body
+            }
+            8 => {
+                type $int = u64;
+                let $a = &*($ptr as *const AtomicU64);
+                $body
+            }
+            _ => unreachable!(),
+        }
+    };
+}
+
+/// A thread-safe mutable memory location for any type.
+///
+/// Types which fit in a native atomic integer and have no drop glue
+/// are accessed with the atomic instructions directly. The other types fall back
+/// to a spinlock protecting the value. [`AtomicCell::is_lock_free`] tells which path is used.
+pub struct AtomicCell<T> {
+    lock: RwSpinlock<()>,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send> Send for AtomicCell<T> {}
+
+unsafe impl<T: Send> Sync for AtomicCell<T> {}
+
+impl<T> AtomicCell<T> {
+    #[inline(always)]
+    pub fn new(value: T) -> Self {
+        Self {
+            lock: RwSpinlock::new(()),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Returns `true` if the operations on `AtomicCell<T>` use the native atomics.
+    #[inline(always)]
+    pub const fn is_lock_free() -> bool {
+        let size = mem::size_of::<T>();
+        let is_native_size = size == 1 || size == 2 || size == 4 || size == 8;
+
+        // The bits of the value are copied around freely,
+        // it is only sound when dropping the old value releases nothing.
+        is_native_size && mem::align_of::<T>() >= size && !mem::needs_drop::<T>()
+    }
+
+    /// Stores the value, the old one is dropped.
+    #[inline]
+    pub fn store(&self, value: T) {
+        drop(self.swap(value));
+    }
+
+    /// Stores the value and returns the old one.
+    pub fn swap(&self, value: T) -> T {
+        if Self::is_lock_free() {
+            let value = ManuallyDrop::new(value);
+            return unsafe {
+                with_atomic!(mem::size_of::<T>(), self.value.get(), a, Int => {
+                    let old = a.swap(mem::transmute_copy::<T, Int>(&value), Ordering::AcqRel);
+                    mem::transmute_copy::<Int, T>(&old)
+                })
+            };
+        }
+
+        let _guard = self.lock.write();
+        unsafe { mem::replace(&mut *self.value.get(), value) }
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<T: Clone> AtomicCell<T> {
+    /// Returns a clone of the value.
+    pub fn load(&self) -> T {
+        if Self::is_lock_free() {
+            return unsafe {
+                with_atomic!(mem::size_of::<T>(), self.value.get(), a, Int => {
+                    let bits = a.load(Ordering::Acquire);
+                    let value = ManuallyDrop::new(mem::transmute_copy::<Int, T>(&bits));
+                    (*value).clone()
+                })
+            };
+        }
+
+        let _guard = self.lock.write();
+        unsafe { (*self.value.get()).clone() }
+    }
+}
+
+impl<T> AtomicCell<Option<T>> {
+    /// Takes the value out, leaving `None` in its place.
+    #[inline]
+    pub fn take(&self) -> Option<T> {
+        self.swap(None)
+    }
+}
+
+impl<T: Default> Default for AtomicCell<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
@@ -0,0 +1,92 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+const CLEAR: u32 = 0;
+const SET: u32 = 1;
+
+/// An event which releases exactly one waiting thread per set, then resets itself.
+///
+/// If nobody is waiting, the event stays set and the next wait consumes it.
+/// It behaves like a [`Semaphore`](crate::semaphore::Semaphore) with at most one permit.
+pub struct AutoResetEvent {
+    state: AtomicU32,
+    waiters: AtomicU32,
+}
+
+impl AutoResetEvent {
+    #[inline(always)]
+    pub const fn new(set: bool) -> Self {
+        Self {
+            state: AtomicU32::new(if set { SET } else { CLEAR }),
+            waiters: AtomicU32::new(0),
+        }
+    }
+
+    /// Sets the event and wakes up one waiting thread if any.
+    #[inline]
+    pub fn set(&self) {
+        if self.state.swap(SET, Ordering::SeqCst) == CLEAR
+            && self.waiters.load(Ordering::SeqCst) > 0 {
+            omango_futex::wake_one(&self.state);
+        }
+    }
+
+    /// Clears a pending set without waking anyone.
+    #[inline(always)]
+    pub fn reset(&self) {
+        self.state.store(CLEAR, Ordering::Relaxed);
+    }
+
+    /// Blocks the current thread until the event is set, then resets it.
+    pub fn wait(&self) {
+        while !self.try_wait() {
+            // The waiter must be published before checking the state again,
+            // so that a concurrent "set" either sees it or the futex sees the new state.
+            self.waiters.fetch_add(1, Ordering::SeqCst);
+            omango_futex::wait(&self.state, CLEAR);
+            self.waiters.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Consumes the event without blocking, returns `false` if it is not set.
+    #[inline]
+    pub fn try_wait(&self) -> bool {
+        self.state.compare_exchange(
+            SET,
+            CLEAR,
+            Ordering::Acquire,
+            Ordering::Relaxed,
+        ).is_ok()
+    }
+
+    #[inline(always)]
+    pub fn is_set(&self) -> bool {
+        self.state.load(Ordering::Relaxed) == SET
+    }
+}
+
+impl Default for AutoResetEvent {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
@@ -0,0 +1,137 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::time::Duration;
+
+// The number of calls which only spin, then the number of calls which yield.
+const SPIN_LIMIT: u32 = 6;
+const YIELD_LIMIT: u32 = 10;
+
+const MIN_SLEEP: Duration = Duration::from_micros(10);
+const MAX_SLEEP: Duration = Duration::from_millis(1);
+
+/// Makes a spin loop wait longer the more often it retries.
+///
+/// [`spin`] spins with an exponentially growing number of *PAUSE* instructions first,
+/// then yields the time slice to the OS scheduler, then sleeps with
+/// an exponentially growing duration up to a cap.
+///
+/// [`pause`] never leaves the CPU, and [`snooze`] never sleeps. They are meant
+/// for loops which must stay on the CPU, or which park on a futex once
+/// [`snooze_completed`] advises it.
+///
+/// [`spin`]: Backoff::spin
+/// [`pause`]: Backoff::pause
+/// [`snooze`]: Backoff::snooze
+/// [`snooze_completed`]: Backoff::snooze_completed
+pub struct Backoff {
+    step: u32,
+    current_sleep: Duration,
+}
+
+impl Backoff {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            step: 0,
+            current_sleep: MIN_SLEEP,
+        }
+    }
+
+    /// Waits for the time appropriate to the number of calls since the last reset.
+    #[inline]
+    pub fn spin(&mut self) {
+        if self.step < SPIN_LIMIT {
+            for _ in 0..1 << self.step {
+                std::hint::spin_loop();
+            }
+        } else if self.step < YIELD_LIMIT {
+            std::thread::yield_now();
+        } else {
+            std::thread::sleep(self.current_sleep);
+            self.current_sleep = (self.current_sleep * 2).min(MAX_SLEEP);
+        }
+        self.step = self.step.saturating_add(1);
+    }
+
+    /// Only spins, the number of *PAUSE* instructions grows up to a cap.
+    ///
+    /// No OS call is ever made.
+    #[inline]
+    pub fn pause(&mut self) {
+        for _ in 0..1 << self.step.min(SPIN_LIMIT) {
+            std::hint::spin_loop();
+        }
+        if self.step <= SPIN_LIMIT {
+            self.step += 1;
+        }
+    }
+
+    /// Spins first, then yields the time slice to the OS scheduler. It never sleeps.
+    #[inline]
+    pub fn snooze(&mut self) {
+        if self.step <= SPIN_LIMIT {
+            for _ in 0..1 << self.step {
+                std::hint::spin_loop();
+            }
+            self.step += 1;
+        } else {
+            std::thread::yield_now();
+        }
+    }
+
+    /// Snoozes, then returns `true` once both the spins and the yields are exhausted,
+    /// which advises to block on a different mechanism instead.
+    #[inline]
+    pub fn snooze_completed(&mut self) -> bool {
+        if self.step <= SPIN_LIMIT {
+            for _ in 0..1 << self.step {
+                std::hint::spin_loop();
+            }
+        } else {
+            std::thread::yield_now();
+        }
+
+        if self.step <= YIELD_LIMIT {
+            self.step += 1;
+            return false;
+        }
+        true
+    }
+
+    #[inline(always)]
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Returns `true` once the sleep has reached its cap,
+    /// which advises to block on a different mechanism instead.
+    #[inline(always)]
+    pub fn is_completed(&self) -> bool {
+        self.current_sleep >= MAX_SLEEP
+    }
+}
+
+impl Default for Backoff {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
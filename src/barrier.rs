@@ -0,0 +1,84 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use crate::backoff::Backoff;
+
+/// A reusable barrier makes `n` threads rendezvous before any of them proceeds.
+///
+/// The generation counter is bumped by the last arriving thread of each cycle,
+/// so the threads of the next cycle never mix with the ones still leaving the current cycle.
+pub struct Barrier {
+    n: u32,
+    count: AtomicU32,
+    generation: AtomicU32,
+}
+
+impl Barrier {
+    #[inline(always)]
+    pub fn new(n: u32) -> Self {
+        Self {
+            n,
+            count: AtomicU32::new(0),
+            generation: AtomicU32::new(0),
+        }
+    }
+
+    /// Blocks the current thread until all `n` threads have called `wait`.
+    ///
+    /// Exactly one thread of each cycle receives a leader result.
+    pub fn wait(&self) -> BarrierWaitResult {
+        // Barrier of zero or one party never blocks.
+        if self.n <= 1 {
+            return BarrierWaitResult(true);
+        }
+
+        let generation = self.generation.load(Ordering::Acquire);
+        if self.count.fetch_add(1, Ordering::AcqRel) + 1 == self.n {
+            // The counter must be reset before releasing the waiters,
+            // they may come back for the next cycle immediately.
+            self.count.store(0, Ordering::Relaxed);
+            self.generation.fetch_add(1, Ordering::Release);
+            omango_futex::wake_all(&self.generation);
+            return BarrierWaitResult(true);
+        }
+
+        let mut backoff = Backoff::new();
+        while self.generation.load(Ordering::Acquire) == generation {
+            if backoff.snooze_completed() {
+                omango_futex::wait(&self.generation, generation);
+            }
+        }
+        BarrierWaitResult(false)
+    }
+}
+
+/// Returned by [`Barrier::wait`] when all threads have arrived.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BarrierWaitResult(pub(crate) bool);
+
+impl BarrierWaitResult {
+    /// Returns `true` for exactly one thread of each cycle.
+    #[inline(always)]
+    pub fn is_leader(&self) -> bool {
+        self.0
+    }
+}
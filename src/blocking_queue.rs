@@ -0,0 +1,148 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+use crate::{condvar::CondVar, mutex::Mutex};
+
+/// A producer-consumer queue, consumers block until an item is available.
+///
+/// An unbounded queue never blocks the producers, a bounded one blocks them
+/// while it is full.
+pub struct BlockingQueue<T> {
+    items: Mutex<VecDeque<T>>,
+    capacity: Option<usize>,
+    not_empty: CondVar,
+    not_full: CondVar,
+}
+
+impl<T> BlockingQueue<T> {
+    #[inline(always)]
+    pub fn new() -> Self {
+        Self {
+            items: Mutex::new(VecDeque::new()),
+            capacity: None,
+            not_empty: CondVar::new(),
+            not_full: CondVar::new(),
+        }
+    }
+
+    /// Creates a queue which holds at most `capacity` items.
+    #[inline]
+    pub fn bounded(capacity: usize) -> Self {
+        assert!(capacity > 0, "capacity must be greater than zero");
+        Self {
+            items: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity: Some(capacity),
+            not_empty: CondVar::new(),
+            not_full: CondVar::new(),
+        }
+    }
+
+    /// Appends an item and wakes up one waiting consumer.
+    ///
+    /// Blocks while a bounded queue is full.
+    pub fn push(&self, value: T) {
+        let mut items = self.items.lock();
+        if let Some(capacity) = self.capacity {
+            while items.len() >= capacity {
+                items = self.not_full.wait(items);
+            }
+        }
+        items.push_back(value);
+        drop(items);
+        self.not_empty.notify_one();
+    }
+
+    /// Removes the oldest item, blocks the current thread until one is available.
+    pub fn pop(&self) -> T {
+        let mut items = self.items.lock();
+        loop {
+            if let Some(value) = items.pop_front() {
+                drop(items);
+                self.not_full.notify_one();
+                return value;
+            }
+            items = self.not_empty.wait(items);
+        }
+    }
+
+    /// Removes the oldest item, returns `None` if none is available before the timeout.
+    pub fn pop_timeout(&self, d: Duration) -> Option<T> {
+        let deadline = Instant::now() + d;
+        let mut items = self.items.lock();
+        loop {
+            if let Some(value) = items.pop_front() {
+                drop(items);
+                self.not_full.notify_one();
+                return Some(value);
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return None;
+            }
+            items = self.not_empty.wait_timeout(items, deadline - now).0;
+        }
+    }
+
+    /// Removes the oldest item without blocking.
+    #[inline]
+    pub fn try_pop(&self) -> Option<T> {
+        let value = self.items.lock().pop_front();
+        if value.is_some() {
+            self.not_full.notify_one();
+        }
+        value
+    }
+
+    /// Removes all items at once and wakes up the blocked producers.
+    pub fn drain(&self) -> Vec<T> {
+        let drained: Vec<T> = self.items.lock().drain(..).collect();
+        if !drained.is_empty() {
+            self.not_full.notify_all();
+        }
+        drained
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.items.lock().len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.items.lock().is_empty()
+    }
+
+    #[inline(always)]
+    pub fn capacity(&self) -> Option<usize> {
+        self.capacity
+    }
+}
+
+impl<T> Default for BlockingQueue<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::Cell,
+    sync::atomic::{AtomicU32, AtomicUsize, Ordering},
+};
+
+/// A one-to-many notification, every broadcast releases all registered waiters.
+///
+/// Each waiter remembers the last generation it has seen, so a broadcast which
+/// happens between two waits is not lost. Several missed broadcasts coalesce
+/// into one, the next wait returns immediately only once.
+pub struct BroadcastBarrier {
+    generation: AtomicU32,
+    registered: AtomicUsize,
+}
+
+impl BroadcastBarrier {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            generation: AtomicU32::new(0),
+            registered: AtomicUsize::new(0),
+        }
+    }
+
+    /// Creates a waiter which is released by the broadcasts from now on.
+    #[inline]
+    pub fn register(&self) -> BroadcastWaiter<'_> {
+        self.registered.fetch_add(1, Ordering::Relaxed);
+        BroadcastWaiter {
+            parent: self,
+            seen: Cell::new(self.generation.load(Ordering::Acquire)),
+        }
+    }
+
+    /// Advances the generation and wakes up all waiters.
+    #[inline]
+    pub fn broadcast(&self) {
+        self.generation.fetch_add(1, Ordering::Release);
+        omango_futex::wake_all(&self.generation);
+    }
+
+    #[inline(always)]
+    pub fn registered(&self) -> usize {
+        self.registered.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for BroadcastBarrier {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The handle of one waiter of a [`BroadcastBarrier`].
+pub struct BroadcastWaiter<'a> {
+    parent: &'a BroadcastBarrier,
+    seen: Cell<u32>,
+}
+
+impl BroadcastWaiter<'_> {
+    /// Blocks the current thread until a broadcast happens
+    /// after the one this waiter has seen last.
+    pub fn wait(&self) {
+        let seen = self.seen.get();
+        loop {
+            let generation = self.parent.generation.load(Ordering::Acquire);
+            if generation != seen {
+                self.seen.set(generation);
+                return;
+            }
+            omango_futex::wait(&self.parent.generation, seen);
+        }
+    }
+}
+
+impl Drop for BroadcastWaiter<'_> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.parent.registered.fetch_sub(1, Ordering::Relaxed);
+    }
+}
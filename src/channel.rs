@@ -0,0 +1,237 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::{Cell, UnsafeCell},
+    marker::PhantomData,
+    mem::MaybeUninit,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering},
+    },
+};
+
+use omango_util::lock::RwSpinlock;
+
+use crate::backoff::Backoff;
+
+/// Creates a bounded multi-producer single-consumer channel.
+///
+/// The channel holds at most `capacity` items, senders are blocked while it is full
+/// and the receiver is blocked while it is empty.
+pub fn bounded<T: Send>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "capacity must be greater than zero");
+
+    let buffer = (0..capacity)
+        .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+        .collect();
+    let shared = Arc::new(Shared {
+        buffer,
+        head: AtomicUsize::new(0),
+        tail: AtomicUsize::new(0),
+        send_lock: RwSpinlock::new(()),
+        senders: AtomicUsize::new(1),
+        disconnected: AtomicBool::new(false),
+        space: AtomicU32::new(0),
+        space_waiters: AtomicU32::new(0),
+        items: AtomicU32::new(0),
+        items_waiters: AtomicU32::new(0),
+    });
+    let receiver = Receiver {
+        shared: shared.clone(),
+        _marker: PhantomData,
+    };
+    (Sender { shared }, receiver)
+}
+
+struct Shared<T> {
+    buffer: Box<[UnsafeCell<MaybeUninit<T>>]>,
+    head: AtomicUsize,
+    tail: AtomicUsize,
+    // Serializes the producers, the single consumer never takes it.
+    send_lock: RwSpinlock<()>,
+    senders: AtomicUsize,
+    disconnected: AtomicBool,
+    // Futex words which are bumped when a slot is freed or an item is pushed.
+    space: AtomicU32,
+    space_waiters: AtomicU32,
+    items: AtomicU32,
+    items_waiters: AtomicU32,
+}
+
+unsafe impl<T: Send> Send for Shared<T> {}
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+impl<T> Shared<T> {
+    #[inline(always)]
+    fn slot(&self, pos: usize) -> *mut MaybeUninit<T> {
+        self.buffer[pos % self.buffer.len()].get()
+    }
+
+    #[inline]
+    fn notify(futex: &AtomicU32, waiters: &AtomicU32) {
+        futex.fetch_add(1, Ordering::SeqCst);
+        if waiters.load(Ordering::SeqCst) > 0 {
+            omango_futex::wake_one(futex);
+        }
+    }
+
+    #[inline]
+    fn park(futex: &AtomicU32, waiters: &AtomicU32, expected: u32) {
+        waiters.fetch_add(1, Ordering::SeqCst);
+        omango_futex::wait(futex, expected);
+        waiters.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+impl<T> Drop for Shared<T> {
+    fn drop(&mut self) {
+        let tail = *self.tail.get_mut();
+        let mut head = *self.head.get_mut();
+        while head != tail {
+            unsafe { (*self.slot(head)).assume_init_drop() };
+            head = head.wrapping_add(1);
+        }
+    }
+}
+
+/// The sending side of the bounded channel.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T: Send> Sender<T> {
+    /// Sends a value, blocks the current thread while the channel is full.
+    ///
+    /// Returns the value back if the receiver was dropped.
+    pub fn send(&self, mut value: T) -> Result<(), T> {
+        let shared = &self.shared;
+        let mut backoff = Backoff::new();
+        loop {
+            // The futex word is read before checking for space,
+            // so a slot freed in between is never missed.
+            let space = shared.space.load(Ordering::SeqCst);
+            value = match self.try_send(value) {
+                Ok(()) => return Ok(()),
+                Err(value) if shared.disconnected.load(Ordering::Acquire) => return Err(value),
+                Err(value) => value,
+            };
+            if !backoff.snooze_completed() {
+                continue;
+            }
+            Shared::<T>::park(&shared.space, &shared.space_waiters, space);
+        }
+    }
+
+    /// Sends a value without blocking.
+    ///
+    /// Returns the value back if the channel is full or the receiver was dropped.
+    pub fn try_send(&self, value: T) -> Result<(), T> {
+        let shared = &self.shared;
+        if shared.disconnected.load(Ordering::Acquire) {
+            return Err(value);
+        }
+        {
+            let _guard = shared.send_lock.write();
+            let tail = shared.tail.load(Ordering::Relaxed);
+            let head = shared.head.load(Ordering::Acquire);
+            if tail.wrapping_sub(head) == shared.buffer.len() {
+                return Err(value);
+            }
+            unsafe { (*shared.slot(tail)).write(value) };
+            shared.tail.store(tail.wrapping_add(1), Ordering::Release);
+        }
+        Shared::<T>::notify(&shared.items, &shared.items_waiters);
+        Ok(())
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        self.shared.senders.fetch_add(1, Ordering::Relaxed);
+        Self {
+            shared: self.shared.clone(),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        if self.shared.senders.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.shared.disconnected.store(true, Ordering::Release);
+            self.shared.items.fetch_add(1, Ordering::SeqCst);
+            omango_futex::wake_all(&self.shared.items);
+        }
+    }
+}
+
+/// The receiving side of the bounded channel.
+///
+/// There is only one receiver, so it can be moved to another thread but not shared.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+    _marker: PhantomData<Cell<()>>,
+}
+
+impl<T: Send> Receiver<T> {
+    /// Receives a value, blocks the current thread while the channel is empty.
+    ///
+    /// Returns `None` once all senders were dropped and the remaining items are drained.
+    pub fn recv(&self) -> Option<T> {
+        let shared = &self.shared;
+        let mut backoff = Backoff::new();
+        loop {
+            let items = shared.items.load(Ordering::SeqCst);
+            if let Some(value) = self.try_recv() {
+                return Some(value);
+            }
+            if shared.disconnected.load(Ordering::Acquire) {
+                // A sender may have pushed right before the last one was dropped.
+                return self.try_recv();
+            }
+            if !backoff.snooze_completed() {
+                continue;
+            }
+            Shared::<T>::park(&shared.items, &shared.items_waiters, items);
+        }
+    }
+
+    /// Receives a value without blocking, returns `None` if the channel is empty.
+    pub fn try_recv(&self) -> Option<T> {
+        let shared = &self.shared;
+        let head = shared.head.load(Ordering::Relaxed);
+        if head == shared.tail.load(Ordering::Acquire) {
+            return None;
+        }
+        let value = unsafe { (*shared.slot(head)).assume_init_read() };
+        shared.head.store(head.wrapping_add(1), Ordering::Release);
+        Shared::<T>::notify(&shared.space, &shared.space_waiters);
+        Some(value)
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.shared.disconnected.store(true, Ordering::Release);
+        self.shared.space.fetch_add(1, Ordering::SeqCst);
+        omango_futex::wake_all(&self.shared.space);
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    sync::atomic::{AtomicU32, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::{futex, mutex::MutexGuard};
+
+/// A condition variable which blocks threads while waiting for an event, paired with [`Mutex`].
+///
+/// Waiting may wake up spuriously, the callers should re-check their predicate in a loop.
+///
+/// [`Mutex`]: crate::mutex::Mutex
+pub struct CondVar {
+    futex: AtomicU32,
+}
+
+impl CondVar {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self { futex: AtomicU32::new(0) }
+    }
+
+    /// Releases the mutex, blocks the current thread until notified
+    /// and re-acquires the mutex before returning.
+    pub fn wait<'a, T>(&self, guard: MutexGuard<'a, T>) -> MutexGuard<'a, T> {
+        // The counter is read before unlocking, so a notification sent
+        // after the unlock changes it and the futex will not sleep.
+        let seq = self.futex.load(Ordering::Relaxed);
+        let mutex = guard.mutex();
+        mutex.raw_unlock();
+        omango_futex::wait(&self.futex, seq);
+        mutex.raw_lock();
+        guard
+    }
+
+    /// The same as [`CondVar::wait`] but gives up after the timeout.
+    ///
+    /// Returns `true` as the second element if the timeout elapsed.
+    pub fn wait_timeout<'a, T>(
+        &self,
+        guard: MutexGuard<'a, T>,
+        d: Duration,
+    ) -> (MutexGuard<'a, T>, bool) {
+        let start = Instant::now();
+        let seq = self.futex.load(Ordering::Relaxed);
+        let mutex = guard.mutex();
+        mutex.raw_unlock();
+        futex::wait_timeout(&self.futex, seq, d);
+        mutex.raw_lock();
+        (guard, start.elapsed() >= d)
+    }
+
+    /// Wakes up one blocked thread.
+    #[inline]
+    pub fn notify_one(&self) {
+        self.futex.fetch_add(1, Ordering::Relaxed);
+        omango_futex::wake_one(&self.futex);
+    }
+
+    /// Wakes up all blocked threads.
+    #[inline]
+    pub fn notify_all(&self) {
+        self.futex.fetch_add(1, Ordering::Relaxed);
+        omango_futex::wake_all(&self.futex);
+    }
+}
+
+impl Default for CondVar {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
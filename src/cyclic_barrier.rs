@@ -0,0 +1,104 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use omango_util::defer::Defer;
+
+use crate::backoff::Backoff;
+
+/// A reusable barrier which runs an action once per cycle, before releasing the threads.
+///
+/// The last arriving thread runs the action, then resets the arrival counter
+/// and advances the generation which opens the gate of the current cycle.
+/// Threads entering the next cycle wait on the new generation,
+/// so they never pass through the gate of the previous one.
+pub struct CyclicBarrier {
+    n: u32,
+    action: Option<fn()>,
+    count: AtomicU32,
+    generation: AtomicU32,
+}
+
+impl CyclicBarrier {
+    #[inline(always)]
+    pub fn new(n: u32, action: Option<fn()>) -> Self {
+        Self {
+            n,
+            action,
+            count: AtomicU32::new(0),
+            generation: AtomicU32::new(0),
+        }
+    }
+
+    /// Blocks the current thread until all `n` threads have arrived.
+    ///
+    /// The action (if any) has completed when this method returns.
+    pub fn await_cycle(&self) -> CyclicBarrierResult {
+        let generation = self.generation.load(Ordering::Acquire);
+        if self.count.fetch_add(1, Ordering::AcqRel) + 1 >= self.n {
+            // The waiters are released even if the action panics.
+            let _release = Defer::new(|| {
+                self.count.store(0, Ordering::Relaxed);
+                self.generation.fetch_add(1, Ordering::Release);
+                omango_futex::wake_all(&self.generation);
+            });
+            if let Some(action) = self.action {
+                action();
+            }
+            return CyclicBarrierResult { is_leader: true, generation };
+        }
+
+        let mut backoff = Backoff::new();
+        while self.generation.load(Ordering::Acquire) == generation {
+            if backoff.snooze_completed() {
+                omango_futex::wait(&self.generation, generation);
+            }
+        }
+        CyclicBarrierResult { is_leader: false, generation }
+    }
+
+    /// Returns the number of the completed cycles.
+    #[inline(always)]
+    pub fn generation(&self) -> u32 {
+        self.generation.load(Ordering::Acquire)
+    }
+}
+
+/// Returned by [`CyclicBarrier::await_cycle`] when all threads have arrived.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CyclicBarrierResult {
+    is_leader: bool,
+    generation: u32,
+}
+
+impl CyclicBarrierResult {
+    /// Returns `true` for the thread which ran the action.
+    #[inline(always)]
+    pub fn is_leader(&self) -> bool {
+        self.is_leader
+    }
+
+    /// Returns the number of the cycle which has just completed.
+    #[inline(always)]
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
@@ -0,0 +1,221 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use omango_util::lock::RwSpinlock;
+
+use crate::{lazy::LazyLock, reentrant_mutex::current_thread_id};
+
+#[derive(Default)]
+struct Graph {
+    // The lock each blocked thread is waiting for.
+    waiting: HashMap<usize, usize>,
+    // The thread holding each lock.
+    holders: HashMap<usize, usize>,
+}
+
+static GRAPH: LazyLock<RwSpinlock<Graph>> = LazyLock::new(|| RwSpinlock::new(Graph::default()));
+
+// The number of registered threads, the hooks return early while it is zero.
+static REGISTERED: AtomicUsize = AtomicUsize::new(0);
+
+thread_local! {
+    static TRACKED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A set of threads which wait for each other in a cycle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadlockCycle {
+    /// The identifiers of the threads in the cycle.
+    pub threads: Vec<usize>,
+    /// The identifiers of the locks the threads are waiting for, in the same order.
+    pub locks: Vec<usize>,
+}
+
+/// A global wait-for graph between the threads, used to find deadlocks.
+///
+/// A thread waiting for a lock has an edge to the thread holding it,
+/// a cycle of edges is a deadlock. The blocking [`Mutex`](crate::mutex::Mutex),
+/// and the [`NamedMutex`](crate::named_mutex::NamedMutex) built on it, report to the graph
+/// in debug builds for the threads which called [`DeadlockDetector::register_current_thread`].
+/// Release builds do not report anything, so they pay no cost.
+pub struct DeadlockDetector;
+
+impl DeadlockDetector {
+    /// Makes the locks of the current thread be reported to the graph.
+    pub fn register_current_thread() {
+        TRACKED.with(|tracked| {
+            if !tracked.replace(true) {
+                REGISTERED.fetch_add(1, Ordering::Relaxed);
+            }
+        });
+    }
+
+    /// Stops reporting the locks of the current thread.
+    pub fn unregister_current_thread() {
+        TRACKED.with(|tracked| {
+            if tracked.replace(false) {
+                REGISTERED.fetch_sub(1, Ordering::Relaxed);
+            }
+        });
+    }
+
+    /// Returns the identifier of the current thread in the graph.
+    #[inline(always)]
+    pub fn current_thread_id() -> usize {
+        current_thread_id()
+    }
+
+    /// Records that `thread_id` is blocked on `lock_id`,
+    /// which adds an edge to the thread holding the lock.
+    pub fn acquire_attempt(thread_id: usize, lock_id: usize) {
+        GRAPH.write().waiting.insert(thread_id, lock_id);
+    }
+
+    /// Records that `thread_id` holds `lock_id`, it is no longer waiting.
+    pub fn acquired(thread_id: usize, lock_id: usize) {
+        let mut graph = GRAPH.write();
+        graph.waiting.remove(&thread_id);
+        graph.holders.insert(lock_id, thread_id);
+    }
+
+    /// Records that `lock_id` is no longer held.
+    pub fn released(lock_id: usize) {
+        GRAPH.write().holders.remove(&lock_id);
+    }
+
+    /// Returns the cycles of the wait-for graph, found with Tarjan's SCC algorithm.
+    pub fn check() -> Vec<DeadlockCycle> {
+        let edges: HashMap<usize, (usize, usize)> = {
+            let graph = GRAPH.read();
+            graph.waiting
+                .iter()
+                .filter_map(|(&thread, &lock)| {
+                    graph.holders.get(&lock).map(|&holder| (thread, (holder, lock)))
+                })
+                .collect()
+        };
+
+        let mut tarjan = Tarjan {
+            edges: &edges,
+            index: HashMap::new(),
+            low: HashMap::new(),
+            stack: Vec::new(),
+            on_stack: HashMap::new(),
+            next_index: 0,
+            cycles: Vec::new(),
+        };
+        let mut threads: Vec<usize> = edges.keys().copied().collect();
+        threads.sort_unstable();
+        for thread in threads {
+            if !tarjan.index.contains_key(&thread) {
+                tarjan.connect(thread);
+            }
+        }
+        tarjan.cycles
+    }
+}
+
+struct Tarjan<'a> {
+    edges: &'a HashMap<usize, (usize, usize)>,
+    index: HashMap<usize, usize>,
+    low: HashMap<usize, usize>,
+    stack: Vec<usize>,
+    on_stack: HashMap<usize, bool>,
+    next_index: usize,
+    cycles: Vec<DeadlockCycle>,
+}
+
+impl Tarjan<'_> {
+    fn connect(&mut self, thread: usize) {
+        self.index.insert(thread, self.next_index);
+        self.low.insert(thread, self.next_index);
+        self.next_index += 1;
+        self.stack.push(thread);
+        self.on_stack.insert(thread, true);
+
+        // Every thread waits for one lock at most, so it has one edge at most.
+        if let Some(&(holder, _)) = self.edges.get(&thread) {
+            if !self.index.contains_key(&holder) {
+                self.connect(holder);
+                let low = self.low[&thread].min(self.low[&holder]);
+                self.low.insert(thread, low);
+            } else if self.on_stack.get(&holder).copied().unwrap_or(false) {
+                let low = self.low[&thread].min(self.index[&holder]);
+                self.low.insert(thread, low);
+            }
+        }
+
+        if self.low[&thread] != self.index[&thread] {
+            return;
+        }
+        let mut component = Vec::new();
+        while let Some(member) = self.stack.pop() {
+            self.on_stack.insert(member, false);
+            component.push(member);
+            if member == thread {
+                break;
+            }
+        }
+
+        let self_loop = self.edges.get(&thread).is_some_and(|&(holder, _)| holder == thread);
+        if component.len() > 1 || self_loop {
+            component.reverse();
+            let locks = component.iter().map(|member| self.edges[member].1).collect();
+            self.cycles.push(DeadlockCycle { threads: component, locks });
+        }
+    }
+}
+
+#[cfg(debug_assertions)]
+#[inline(always)]
+fn tracked() -> bool {
+    REGISTERED.load(Ordering::Relaxed) > 0 && TRACKED.with(|tracked| tracked.get())
+}
+
+#[cfg(debug_assertions)]
+#[inline]
+pub(crate) fn on_lock_attempt(lock_id: usize) {
+    if tracked() {
+        DeadlockDetector::acquire_attempt(current_thread_id(), lock_id);
+    }
+}
+
+#[cfg(debug_assertions)]
+#[inline]
+pub(crate) fn on_locked(lock_id: usize) {
+    if tracked() {
+        DeadlockDetector::acquired(current_thread_id(), lock_id);
+    }
+}
+
+#[cfg(debug_assertions)]
+#[inline]
+pub(crate) fn on_unlocked(lock_id: usize) {
+    // The lock may be released by another thread than the one which acquired it.
+    if REGISTERED.load(Ordering::Relaxed) > 0 {
+        DeadlockDetector::released(lock_id);
+    }
+}
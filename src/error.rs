@@ -0,0 +1,304 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    any::Any,
+    collections::HashMap,
+    fmt,
+    hash::{Hash, Hasher},
+    time::Duration,
+};
+
+use crate::lazy::LazyLock;
+
+static EMPTY_CONTEXT: LazyLock<HashMap<String, String>> = LazyLock::new(HashMap::new);
+
+/// The category of an [`Error`], to handle it without inspecting the message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// The user function panicked.
+    Panic,
+    /// The operation did not complete in time.
+    Timeout,
+    /// The operation was abandoned before it completed.
+    Cancelled,
+    /// The primitive was misused by the caller.
+    UserError,
+    /// A broken invariant of the crate.
+    InternalError,
+}
+
+/// The error which is delivered by the synchronization primitives.
+///
+/// Two errors are equal if they have the same kind and message,
+/// the sources and the context are not compared.
+#[derive(Debug)]
+pub struct Error {
+    pub kind: ErrorKind,
+    pub message: String,
+    // The optional parts are boxed, so the common errors stay small.
+    details: Option<Box<Details>>,
+}
+
+#[derive(Debug, Default)]
+struct Details {
+    source: Option<Box<dyn std::error::Error + Send + Sync + 'static>>,
+    context: HashMap<String, String>,
+    hint: Option<String>,
+    timeout: Option<Duration>,
+}
+
+impl Error {
+    #[inline]
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    /// Creates an error caused by `source`, which is kept in the error chain.
+    #[inline]
+    pub fn wrap(
+        message: impl Into<String>,
+        source: impl std::error::Error + Send + Sync + 'static,
+    ) -> Self {
+        let mut error = Self::new(ErrorKind::UserError, message);
+        error.details_mut().source = Some(Box::new(source));
+        error
+    }
+
+    #[inline]
+    pub fn panic(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Panic, message)
+    }
+
+    #[inline]
+    pub fn timeout(message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Timeout, message)
+    }
+
+    /// Creates a timeout error which records the configured timeout.
+    #[inline]
+    pub fn timeout_after(duration: Duration, message: impl Into<String>) -> Self {
+        let mut error = Self::timeout(message);
+        error.details_mut().timeout = Some(duration);
+        error
+    }
+
+    #[inline]
+    pub fn cancelled() -> Self {
+        Self::new(ErrorKind::Cancelled, "operation cancelled")
+    }
+
+    /// Wraps the error as the source of a new one with the same kind,
+    /// to annotate it at the call site.
+    #[inline]
+    pub fn context(self, message: impl Into<String>) -> Self {
+        let mut error = Self::new(self.kind, message);
+        error.details_mut().source = Some(Box::new(self));
+        error
+    }
+
+    /// Attaches a key-value pair describing the circumstances of the error.
+    #[inline]
+    pub fn with_context(mut self, key: impl Into<String>, val: impl Into<String>) -> Self {
+        self.details_mut().context.insert(key.into(), val.into());
+        self
+    }
+
+    /// Sets a guidance for the operator on how to fix the error.
+    #[inline]
+    pub fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.details_mut().hint = Some(hint.into());
+        self
+    }
+
+    #[inline(always)]
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    #[inline(always)]
+    pub fn is_panic(&self) -> bool {
+        self.kind == ErrorKind::Panic
+    }
+
+    #[inline(always)]
+    pub fn is_timeout(&self) -> bool {
+        self.kind == ErrorKind::Timeout
+    }
+
+    #[inline(always)]
+    pub fn is_cancelled(&self) -> bool {
+        self.kind == ErrorKind::Cancelled
+    }
+
+    #[inline(always)]
+    pub fn context_map(&self) -> &HashMap<String, String> {
+        self.details.as_ref().map_or(&EMPTY_CONTEXT, |details| &details.context)
+    }
+
+    #[inline(always)]
+    pub fn hint(&self) -> Option<&str> {
+        self.details.as_ref().and_then(|details| details.hint.as_deref())
+    }
+
+    /// Returns the timeout which elapsed, if the error was created with it.
+    #[inline(always)]
+    pub fn timed_out_after(&self) -> Option<Duration> {
+        self.details.as_ref().and_then(|details| details.timeout)
+    }
+
+    /// Consumes the error and returns its source, to downcast it to the original error.
+    #[inline]
+    pub fn into_source(self) -> Option<Box<dyn std::error::Error + Send + Sync + 'static>> {
+        self.details.and_then(|details| details.source)
+    }
+
+    #[inline]
+    fn details_mut(&mut self) -> &mut Details {
+        self.details.get_or_insert_with(Default::default)
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)?;
+        if !self.context_map().is_empty() {
+            // The keys are sorted, so the same error is always displayed the same way.
+            let mut entries: Vec<_> = self.context_map().iter().collect();
+            entries.sort_unstable();
+            f.write_str(" [")?;
+            for (idx, (key, val)) in entries.into_iter().enumerate() {
+                if idx > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{}={}", key, val)?;
+            }
+            f.write_str("]")?;
+        }
+        if let Some(hint) = self.hint() {
+            write!(f, " (Hint: {})", hint)?;
+        }
+        Ok(())
+    }
+}
+
+/// Converts the payload of a caught panic, keeping its message when it is a string.
+impl From<Box<dyn Any + Send>> for Error {
+    fn from(payload: Box<dyn Any + Send>) -> Self {
+        let message = match payload.downcast::<&'static str>() {
+            Ok(message) => (*message).to_string(),
+            Err(payload) => match payload.downcast::<String>() {
+                Ok(message) => *message,
+                Err(_) => "<opaque panic payload>".to_string(),
+            },
+        };
+        Self::panic(message)
+    }
+}
+
+impl PartialEq for Error {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.kind == other.kind && self.message == other.message
+    }
+}
+
+impl Eq for Error {}
+
+/// Hashes the same fields as the equality, so equal errors have equal hashes.
+impl Hash for Error {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.kind.hash(state);
+        self.message.hash(state);
+    }
+}
+
+impl std::error::Error for Error {
+    #[inline]
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.details
+            .as_ref()
+            .and_then(|details| details.source.as_ref())
+            .map(|source| source.as_ref() as _)
+    }
+}
+
+/// The errors collected by an operation which does not stop at the first failure.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ErrorVec(pub Vec<Error>);
+
+impl ErrorVec {
+    #[inline(always)]
+    pub fn push(&mut self, e: Error) {
+        self.0.push(e);
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+impl fmt::Display for ErrorVec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (idx, error) in self.0.iter().enumerate() {
+            if idx > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{}. {}", idx + 1, error)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ErrorVec {}
+
+impl From<Vec<Error>> for ErrorVec {
+    #[inline(always)]
+    fn from(errors: Vec<Error>) -> Self {
+        Self(errors)
+    }
+}
+
+/// Joins the messages with "; ", the kind is kept if all errors share it.
+impl From<ErrorVec> for Error {
+    fn from(errors: ErrorVec) -> Self {
+        let kind = match errors.0.split_first() {
+            Some((first, rest)) if rest.iter().all(|e| e.kind == first.kind) => first.kind,
+            _ => ErrorKind::UserError,
+        };
+        let message = errors.0
+            .iter()
+            .map(|e| e.message.as_str())
+            .collect::<Vec<_>>()
+            .join("; ");
+        Error::new(kind, message)
+    }
+}
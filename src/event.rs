@@ -0,0 +1,69 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+const CLOSED: u32 = 0;
+const OPEN: u32 = 1;
+
+/// A one-shot event which starts closed and is opened exactly once.
+///
+/// All threads waiting on the event are released when it is signaled,
+/// the later waits return immediately.
+pub struct Event {
+    state: AtomicU32,
+}
+
+impl Event {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self { state: AtomicU32::new(CLOSED) }
+    }
+
+    /// Opens the event and wakes up all waiting threads.
+    ///
+    /// Only the first call has effect, the subsequent ones are no-ops.
+    #[inline]
+    pub fn signal(&self) {
+        if self.state.swap(OPEN, Ordering::Release) == CLOSED {
+            omango_futex::wake_all(&self.state);
+        }
+    }
+
+    /// Blocks the current thread until the event is signaled.
+    #[inline]
+    pub fn wait(&self) {
+        while self.state.load(Ordering::Acquire) == CLOSED {
+            omango_futex::wait(&self.state, CLOSED);
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_signaled(&self) -> bool {
+        self.state.load(Ordering::Acquire) == OPEN
+    }
+}
+
+impl Default for Event {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,126 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+const EMPTY: u32 = 0;
+const BUSY: u32 = 1;
+const OFFERED: u32 = 2;
+const EXCHANGED: u32 = 3;
+
+/// A synchronization point where two threads swap their values.
+///
+/// The first thread offers its value and parks, the second one takes it,
+/// leaves its own value in the slot and wakes the first one up.
+/// Other threads arriving in the middle of a swap wait until the pair has finished.
+pub struct Exchanger<T> {
+    state: AtomicU32,
+    slot: UnsafeCell<MaybeUninit<T>>,
+}
+
+unsafe impl<T: Send> Send for Exchanger<T> {}
+
+unsafe impl<T: Send> Sync for Exchanger<T> {}
+
+impl<T> Exchanger<T> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            state: AtomicU32::new(EMPTY),
+            slot: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    /// Blocks the current thread until a partner arrives,
+    /// then returns the value given by the partner.
+    pub fn exchange(&self, value: T) -> T {
+        loop {
+            match self.state.load(Ordering::Acquire) {
+                EMPTY => {
+                    if self.state.compare_exchange(
+                        EMPTY,
+                        BUSY,
+                        Ordering::Acquire,
+                        Ordering::Relaxed,
+                    ).is_ok() {
+                        return self.offer(value);
+                    }
+                }
+                OFFERED => {
+                    if self.state.compare_exchange(
+                        OFFERED,
+                        BUSY,
+                        Ordering::Acquire,
+                        Ordering::Relaxed,
+                    ).is_ok() {
+                        return self.take(value);
+                    }
+                }
+                state => {
+                    omango_futex::wait(&self.state, state);
+                }
+            }
+        }
+    }
+
+    /// Stores the value, waits for a partner and returns its value.
+    fn offer(&self, value: T) -> T {
+        unsafe { (*self.slot.get()).write(value) };
+        self.set_state(OFFERED);
+
+        // Only the partner changes the state from now on.
+        loop {
+            let state = self.state.load(Ordering::Acquire);
+            if state == EXCHANGED {
+                break;
+            }
+            omango_futex::wait(&self.state, state);
+        }
+
+        let value = unsafe { (*self.slot.get()).assume_init_read() };
+        self.set_state(EMPTY);
+        value
+    }
+
+    /// Swaps the offered value with the given one and wakes the offering thread up.
+    fn take(&self, value: T) -> T {
+        let offered = unsafe { std::mem::replace(&mut *self.slot.get(), MaybeUninit::new(value)) };
+        self.set_state(EXCHANGED);
+        unsafe { offered.assume_init() }
+    }
+
+    #[inline(always)]
+    fn set_state(&self, state: u32) {
+        // Both the partner and the threads waiting for the next turn are parked on the state.
+        self.state.store(state, Ordering::Release);
+        omango_futex::wake_all(&self.state);
+    }
+}
+
+impl<T> Default for Exchanger<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,235 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::UnsafeCell,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    ptr,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use crate::ticket_lock::TicketLock;
+
+const WAITING: u32 = 0;
+const GRANTED: u32 = 1;
+
+/// A blocking mutual exclusion lock which grants the lock in FIFO order.
+///
+/// Contended threads append a node allocated on their own stack to the waiter queue.
+/// On unlock, the ownership is handed over to the head of the queue directly,
+/// so the longest-waiting thread always acquires the lock next and no thread starves.
+pub struct FairMutex<T> {
+    queue: TicketLock<Queue>,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send> Send for FairMutex<T> {}
+
+unsafe impl<T: Send> Sync for FairMutex<T> {}
+
+impl<T> FairMutex<T> {
+    #[inline(always)]
+    pub const fn new(value: T) -> Self {
+        Self {
+            queue: TicketLock::new(Queue {
+                locked: false,
+                waiters: WaiterQueue::new(),
+            }),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Acquires the lock, blocks the current thread until it is able to do so.
+    pub fn lock(&self) -> FairMutexGuard<'_, T> {
+        // The node lives in this frame and is never moved
+        // until the owner removes it from the queue and grants the lock.
+        let node = WaiterNode::new();
+
+        {
+            let mut queue = self.queue.lock();
+            if !queue.locked {
+                queue.locked = true;
+                return FairMutexGuard { parent: self, _marker: PhantomData };
+            }
+            queue.waiters.push(&node);
+        }
+
+        node.wait();
+        FairMutexGuard { parent: self, _marker: PhantomData }
+    }
+
+    /// Acquires the lock without blocking.
+    #[inline]
+    pub fn try_lock(&self) -> Option<FairMutexGuard<'_, T>> {
+        let mut queue = self.queue.lock();
+        if queue.locked {
+            return None;
+        }
+        queue.locked = true;
+        Some(FairMutexGuard { parent: self, _marker: PhantomData })
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+
+    fn unlock(&self) {
+        let mut queue = self.queue.lock();
+        match queue.waiters.pop() {
+            // The lock stays locked, its ownership is handed over to the waiter.
+            Some(node) => unsafe { WaiterNode::grant(node) },
+            None => queue.locked = false,
+        }
+    }
+}
+
+impl<T: Default> Default for FairMutex<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+struct Queue {
+    locked: bool,
+    waiters: WaiterQueue,
+}
+
+/// The node of a blocked thread, allocated on its own stack.
+pub(crate) struct WaiterNode {
+    next: UnsafeCell<*const WaiterNode>,
+    state: AtomicU32,
+}
+
+impl WaiterNode {
+    #[inline(always)]
+    pub(crate) fn new() -> Self {
+        Self {
+            next: UnsafeCell::new(ptr::null()),
+            state: AtomicU32::new(WAITING),
+        }
+    }
+
+    /// Blocks the current thread until the node is granted.
+    #[inline]
+    pub(crate) fn wait(&self) {
+        while self.state.load(Ordering::Acquire) == WAITING {
+            omango_futex::wait(&self.state, WAITING);
+        }
+    }
+
+    /// Grants the node and wakes up its thread.
+    ///
+    /// The node must have been removed from its queue.
+    #[inline]
+    pub(crate) unsafe fn grant(node: *const WaiterNode) {
+        let state: *const AtomicU32 = unsafe { &(*node).state };
+        unsafe { (*state).store(GRANTED, Ordering::Release) };
+
+        // The waiter may have returned and released its node already,
+        // waking a dangling address is fine.
+        omango_futex::wake_one(state);
+    }
+}
+
+/// The intrusive FIFO queue of the waiters, it must be protected by a lock.
+pub(crate) struct WaiterQueue {
+    head: *const WaiterNode,
+    tail: *const WaiterNode,
+}
+
+unsafe impl Send for WaiterQueue {}
+
+impl WaiterQueue {
+    #[inline(always)]
+    pub(crate) const fn new() -> Self {
+        Self {
+            head: ptr::null(),
+            tail: ptr::null(),
+        }
+    }
+
+    #[inline(always)]
+    pub(crate) fn is_empty(&self) -> bool {
+        self.head.is_null()
+    }
+
+    #[inline]
+    pub(crate) fn push(&mut self, node: *const WaiterNode) {
+        if self.tail.is_null() {
+            self.head = node;
+        } else {
+            unsafe { *(*self.tail).next.get() = node };
+        }
+        self.tail = node;
+    }
+
+    #[inline]
+    pub(crate) fn pop(&mut self) -> Option<*const WaiterNode> {
+        if self.head.is_null() {
+            return None;
+        }
+        let node = self.head;
+        self.head = unsafe { *(*node).next.get() };
+        if self.head.is_null() {
+            self.tail = ptr::null();
+        }
+        Some(node)
+    }
+}
+
+/// Releases the [`FairMutex`] on drop, handing it over to the next waiter if any.
+pub struct FairMutexGuard<'a, T> {
+    parent: &'a FairMutex<T>,
+    // The guard must be dropped by the thread which locked the mutex.
+    _marker: PhantomData<*const ()>,
+}
+
+unsafe impl<T: Sync> Sync for FairMutexGuard<'_, T> {}
+
+impl<T> Drop for FairMutexGuard<'_, T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.parent.unlock();
+    }
+}
+
+impl<T> Deref for FairMutexGuard<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        unsafe { &*self.parent.value.get() }
+    }
+}
+
+impl<T> DerefMut for FairMutexGuard<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.parent.value.get() }
+    }
+}
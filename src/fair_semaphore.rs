@@ -0,0 +1,116 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use omango_util::lock::RwSpinlock;
+
+use crate::fair_mutex::{WaiterNode, WaiterQueue};
+
+/// A counting semaphore which grants the permits in FIFO order.
+///
+/// Threads which can not get a permit append a node allocated on their own stack
+/// to the waiter queue. A released permit is handed over to the head of the queue
+/// directly, so late comers can never overtake the waiting threads.
+pub struct FairSemaphore {
+    inner: RwSpinlock<Inner>,
+}
+
+struct Inner {
+    permits: u32,
+    waiters: WaiterQueue,
+}
+
+impl FairSemaphore {
+    #[inline(always)]
+    pub fn new(permits: u32) -> Self {
+        Self {
+            inner: RwSpinlock::new(Inner {
+                permits,
+                waiters: WaiterQueue::new(),
+            }),
+        }
+    }
+
+    /// Takes one permit, blocks the current thread until it is its turn.
+    pub fn acquire(&self) {
+        // The node lives in this frame and is never moved
+        // until a releasing thread removes it from the queue and grants it a permit.
+        let node = WaiterNode::new();
+        {
+            let mut inner = self.inner.write();
+            if inner.permits > 0 {
+                inner.permits -= 1;
+                return;
+            }
+            inner.waiters.push(&node);
+        }
+        node.wait();
+    }
+
+    /// Takes one permit and returns a guard which gives it back on drop.
+    #[inline]
+    pub fn acquire_guard(&self) -> FairSemaphoreGuard<'_> {
+        self.acquire();
+        FairSemaphoreGuard { parent: self }
+    }
+
+    /// Takes one permit without blocking.
+    ///
+    /// Returns `false` if there is no available permit.
+    #[inline]
+    pub fn try_acquire(&self) -> bool {
+        let mut inner = self.inner.write();
+        if inner.permits == 0 {
+            return false;
+        }
+        inner.permits -= 1;
+        true
+    }
+
+    /// Gives back one permit, it is handed over to the longest-waiting thread if any.
+    pub fn release(&self) {
+        let mut inner = self.inner.write();
+        match inner.waiters.pop() {
+            Some(node) => unsafe { WaiterNode::grant(node) },
+            None => inner.permits += 1,
+        }
+    }
+
+    #[inline]
+    pub fn available_permits(&self) -> u32 {
+        self.inner.read().permits
+    }
+
+    #[inline]
+    pub fn has_waiters(&self) -> bool {
+        !self.inner.read().waiters.is_empty()
+    }
+}
+
+/// Gives back the permit to the [`FairSemaphore`] on drop.
+pub struct FairSemaphoreGuard<'a> {
+    parent: &'a FairSemaphore,
+}
+
+impl Drop for FairSemaphoreGuard<'_> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.parent.release();
+    }
+}
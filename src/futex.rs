@@ -0,0 +1,57 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{sync::atomic::AtomicU32, time::Duration};
+
+/// If the value is `expected`, wait until woken up or the timeout elapsed.
+///
+/// This function might also return spuriously,
+/// without a corresponding wake operation.
+///
+/// `omango_futex::wait_until` passes an absolute deadline which points to a dropped stack value
+/// on Linux, so the relative timeout syscall is called directly here.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+#[inline]
+pub(crate) fn wait_timeout(atom: &AtomicU32, expected: u32, timeout: Duration) -> bool {
+    let timespec = libc::timespec {
+        tv_sec: timeout.as_secs().min(libc::time_t::MAX as u64) as libc::time_t,
+        tv_nsec: timeout.subsec_nanos() as libc::c_long,
+    };
+    let code = unsafe {
+        libc::syscall(
+            libc::SYS_futex,
+            atom,
+            libc::FUTEX_WAIT | libc::FUTEX_PRIVATE_FLAG,
+            expected,
+            &timespec as *const libc::timespec,
+        )
+    };
+    code == 0
+}
+
+/// If the value is `expected`, wait until woken up or the timeout elapsed.
+///
+/// This function might also return spuriously,
+/// without a corresponding wake operation.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+#[inline]
+pub(crate) fn wait_timeout(atom: &AtomicU32, expected: u32, timeout: Duration) -> bool {
+    omango_futex::wait_until(atom, expected, timeout.as_millis().min(u32::MAX as u128) as u32)
+}
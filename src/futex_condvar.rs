@@ -0,0 +1,74 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// A condition variable which works directly on a futex word, without a mutex.
+///
+/// The condition is expressed on the value of the word itself, so it is the
+/// lowest-level building block of the blocking primitives of the crate:
+///
+/// - [`Event`](crate::event::Event) waits while the word is closed and
+///   notifies all with the open value.
+/// - [`CountdownLatch`](crate::latch::CountdownLatch) waits while the word is not open
+///   and the last count-down notifies all.
+/// - [`Gate`](crate::gate::Gate) waits while the word keeps the value it had at the start
+///   and each opening notifies all with a new value.
+/// - [`AutoResetEvent`](crate::auto_reset_event::AutoResetEvent) waits while the word is clear
+///   and notifies one with the set value.
+#[derive(Debug, Default)]
+pub struct FutexCondVar;
+
+impl FutexCondVar {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Blocks the current thread while `predicate` holds for the value of `atom`.
+    ///
+    /// The value is checked again after every wake up, so spurious wake ups are harmless.
+    #[inline]
+    pub fn wait_while<F: Fn(u32) -> bool>(&self, atom: &AtomicU32, predicate: F) {
+        loop {
+            // The futex only parks if the word still holds the checked value,
+            // so a notification between the check and the wait is never missed.
+            let value = atom.load(Ordering::Acquire);
+            if !predicate(value) {
+                return;
+            }
+            omango_futex::wait(atom, value);
+        }
+    }
+
+    /// Stores `new_val` into `atom` and wakes up one waiting thread.
+    #[inline]
+    pub fn notify_one(&self, atom: &AtomicU32, new_val: u32) {
+        atom.store(new_val, Ordering::Release);
+        omango_futex::wake_one(atom);
+    }
+
+    /// Stores `new_val` into `atom` and wakes up all waiting threads.
+    #[inline]
+    pub fn notify_all(&self, atom: &AtomicU32, new_val: u32) {
+        atom.store(new_val, Ordering::Release);
+        omango_futex::wake_all(atom);
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+// The lowest bit is the open flag, the other bits count how many times the gate was opened.
+const OPEN: u32 = 1;
+const ONE_OPENING: u32 = 2;
+
+/// A gate which blocks threads while it is closed and can be reopened repeatedly.
+///
+/// Threads which are waiting when the gate opens are all released,
+/// even if the gate is closed again before they are scheduled.
+pub struct Gate {
+    state: AtomicU32,
+}
+
+impl Gate {
+    #[inline(always)]
+    pub const fn new(open: bool) -> Self {
+        Self {
+            state: AtomicU32::new(if open { OPEN } else { 0 }),
+        }
+    }
+
+    /// Opens the gate and wakes up all waiting threads.
+    pub fn open(&self) {
+        let mut state = self.state.load(Ordering::Relaxed);
+        loop {
+            if state & OPEN != 0 {
+                return;
+            }
+            match self.state.compare_exchange_weak(
+                state,
+                state.wrapping_add(ONE_OPENING) | OPEN,
+                Ordering::Release,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => break,
+                Err(current) => state = current,
+            }
+        }
+        omango_futex::wake_all(&self.state);
+    }
+
+    /// Closes the gate, the next waits block until it is opened again.
+    #[inline]
+    pub fn close(&self) {
+        self.state.fetch_and(!OPEN, Ordering::Relaxed);
+    }
+
+    /// Blocks the current thread while the gate is closed.
+    pub fn wait(&self) {
+        let start = self.state.load(Ordering::Acquire);
+        if start & OPEN != 0 {
+            return;
+        }
+        let mut state = start;
+        loop {
+            omango_futex::wait(&self.state, state);
+            state = self.state.load(Ordering::Acquire);
+            // Any change means the gate was opened since the start,
+            // which releases the thread even if it is closed again.
+            if state != start {
+                return;
+            }
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_open(&self) -> bool {
+        self.state.load(Ordering::Acquire) & OPEN != 0
+    }
+}
@@ -0,0 +1,321 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{marker::PhantomData, ptr, sync::atomic::Ordering};
+
+use crate::{
+    lazy::LazyLock,
+    shim::{AtomicBool, AtomicPtr, AtomicUsize, RwSpinlock, fence},
+};
+
+// The minimum number of retired objects which triggers a reclamation,
+// it grows with the number of slots so a scan frees a batch of objects.
+const RETIRE_THRESHOLD: usize = 64;
+
+static GLOBAL: LazyLock<HazardDomain> = LazyLock::new(HazardDomain::new);
+
+struct Retired {
+    ptr: *mut (),
+    deleter: Box<dyn FnOnce() + Send>,
+}
+
+/// A protection slot. Slots are only ever added to the domain's list,
+/// the `active` flag tells whether a record owns the slot.
+struct Slot {
+    ptr: AtomicPtr<()>,
+    active: AtomicBool,
+    next: *const Slot,
+}
+
+/// A hazard pointer domain which defers the reclamation of retired objects
+/// until no thread protects them anymore.
+///
+/// The number of slots grows on demand, so any number of pointers
+/// can be protected at the same time.
+pub struct HazardDomain {
+    slots: AtomicPtr<Slot>,
+    slot_count: AtomicUsize,
+    retired: RwSpinlock<Vec<Retired>>,
+}
+
+// The retired objects are `Send` and their deleters may run on any thread.
+unsafe impl Send for HazardDomain {}
+
+unsafe impl Sync for HazardDomain {}
+
+impl HazardDomain {
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            slots: AtomicPtr::new(ptr::null_mut()),
+            slot_count: AtomicUsize::new(0),
+            retired: RwSpinlock::new(Vec::new()),
+        }
+    }
+
+    /// Returns the domain which is shared by the whole process.
+    #[inline(always)]
+    pub fn global() -> &'static HazardDomain {
+        &GLOBAL
+    }
+
+    /// Marks `ptr` as protected, it is not reclaimed until the record is dropped.
+    ///
+    /// The caller must check that `ptr` is still reachable after protecting it,
+    /// since it may have been retired in between. [`HazardDomain::protect_load`] does it.
+    ///
+    /// A null pointer is allowed, the record keeps its slot and can be [`reset`]
+    /// to another pointer later.
+    ///
+    /// [`reset`]: HazardRecord::reset
+    pub fn protect<T>(&self, ptr: *const T) -> HazardRecord<'_, T> {
+        let slot = self.acquire_slot();
+        slot.ptr.store(ptr as *mut (), Ordering::Relaxed);
+        // Pairs with the fence of the scan: either the scan sees the record,
+        // or the validating load after this one sees the pointer was replaced.
+        fence(Ordering::SeqCst);
+        HazardRecord { slot, ptr }
+    }
+
+    /// Loads the pointer from `src` and protects it,
+    /// retries until the protected pointer is still the one stored in `src`.
+    pub fn protect_load<T>(&self, src: &AtomicPtr<T>) -> HazardRecord<'_, T> {
+        let mut record = self.protect(src.load(Ordering::Acquire));
+        loop {
+            let current = src.load(Ordering::Acquire);
+            if ptr::eq(current, record.ptr) {
+                return record;
+            }
+            record.reset(current);
+        }
+    }
+
+    /// Retires `ptr`, `deleter` is called once no record protects it anymore.
+    ///
+    /// The deleter may run on any thread, hence `T` must be `Send`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must be unreachable for new readers and must not be retired twice.
+    pub unsafe fn retire<T: Send + 'static>(&self, ptr: *mut T, deleter: unsafe fn(*mut T)) {
+        let item = SendPtr(ptr);
+        let len = {
+            let mut retired = self.retired.write();
+            retired.push(Retired {
+                ptr: ptr as *mut (),
+                deleter: Box::new(move || unsafe { deleter(item.into_inner()) }),
+            });
+            retired.len()
+        };
+        if len >= self.retire_threshold() {
+            self.flush();
+        }
+    }
+
+    /// Returns `true` if a record currently protects `ptr`.
+    #[inline]
+    pub fn is_protected<T>(&self, ptr: *const T) -> bool {
+        // Pairs with the protection, see "flush".
+        fence(Ordering::SeqCst);
+        self.iter()
+            .any(|slot| ptr::eq(slot.ptr.load(Ordering::Acquire), ptr as *mut ()))
+    }
+
+    /// Reclaims the retired objects which are not protected.
+    pub fn flush(&self) {
+        // Pairs with the protection, so either the scan sees the slot
+        // or the reader sees that the pointer is no longer reachable.
+        fence(Ordering::SeqCst);
+
+        let protected: Vec<*mut ()> = self
+            .iter()
+            .map(|slot| slot.ptr.load(Ordering::Acquire))
+            .filter(|ptr| !ptr.is_null())
+            .collect();
+
+        let reclaimable: Vec<Retired> = {
+            let mut retired = self.retired.write();
+            let (free, kept) = retired
+                .drain(..)
+                .partition(|item| !protected.contains(&item.ptr));
+            *retired = kept;
+            free
+        };
+        for item in reclaimable {
+            (item.deleter)();
+        }
+    }
+
+    /// Returns the number of retired objects which are not reclaimed yet.
+    #[inline]
+    pub fn retired_count(&self) -> usize {
+        self.retired.read().len()
+    }
+
+    /// Returns the number of slots allocated by the domain.
+    #[inline(always)]
+    pub fn slot_count(&self) -> usize {
+        self.slot_count.load(Ordering::Relaxed)
+    }
+
+    /// Claims a free slot, allocates a new one if all of them are in use.
+    fn acquire_slot(&self) -> &Slot {
+        for slot in self.iter() {
+            if !slot.active.load(Ordering::Relaxed)
+                && slot
+                    .active
+                    .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                    .is_ok()
+            {
+                return slot;
+            }
+        }
+
+        let slot = Box::into_raw(Box::new(Slot {
+            ptr: AtomicPtr::new(ptr::null_mut()),
+            active: AtomicBool::new(true),
+            next: ptr::null(),
+        }));
+        let mut head = self.slots.load(Ordering::Acquire);
+        loop {
+            // The slot is not published yet, nobody else sees it.
+            unsafe { (*slot).next = head };
+            match self.slots.compare_exchange_weak(
+                head,
+                slot,
+                Ordering::AcqRel,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => break,
+                Err(current) => head = current,
+            }
+        }
+        self.slot_count.fetch_add(1, Ordering::Relaxed);
+        // Slots are only freed with the domain, which outlives the borrow.
+        unsafe { &*slot }
+    }
+
+    #[inline]
+    fn retire_threshold(&self) -> usize {
+        RETIRE_THRESHOLD.max(2 * self.slot_count())
+    }
+
+    #[inline]
+    fn iter(&self) -> SlotIter<'_> {
+        SlotIter {
+            next: self.slots.load(Ordering::Acquire),
+            _domain: PhantomData,
+        }
+    }
+}
+
+impl Default for HazardDomain {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for HazardDomain {
+    fn drop(&mut self) {
+        for item in self.retired.write().drain(..) {
+            (item.deleter)();
+        }
+
+        // No record outlives the domain, so every slot is free.
+        let mut next = self.slots.load(Ordering::Relaxed) as *const Slot;
+        while !next.is_null() {
+            let slot = unsafe { Box::from_raw(next as *mut Slot) };
+            next = slot.next;
+        }
+    }
+}
+
+struct SlotIter<'a> {
+    next: *const Slot,
+    _domain: PhantomData<&'a HazardDomain>,
+}
+
+impl<'a> Iterator for SlotIter<'a> {
+    type Item = &'a Slot;
+
+    #[inline]
+    fn next(&mut self) -> Option<&'a Slot> {
+        if self.next.is_null() {
+            return None;
+        }
+        let slot = unsafe { &*self.next };
+        self.next = slot.next;
+        Some(slot)
+    }
+}
+
+/// Moves the retired pointer to the thread which runs its deleter.
+struct SendPtr<T>(*mut T);
+
+unsafe impl<T: Send> Send for SendPtr<T> {}
+
+impl<T> SendPtr<T> {
+    // Takes `self` so the closure captures the whole wrapper, not the raw field.
+    #[inline(always)]
+    fn into_inner(self) -> *mut T {
+        self.0
+    }
+}
+
+/// Protects a pointer from reclamation until it is dropped.
+pub struct HazardRecord<'a, T> {
+    slot: &'a Slot,
+    ptr: *const T,
+}
+
+impl<T> HazardRecord<'_, T> {
+    #[inline(always)]
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr
+    }
+
+    /// Moves the protection to another pointer, keeping the same slot.
+    #[inline]
+    pub fn reset(&mut self, ptr: *const T) {
+        self.slot.ptr.store(ptr as *mut (), Ordering::Relaxed);
+        // See `HazardDomain::protect`.
+        fence(Ordering::SeqCst);
+        self.ptr = ptr;
+    }
+
+    /// Returns a reference to the protected object.
+    ///
+    /// # Safety
+    ///
+    /// The pointer must be non-null and must have been reachable after it was protected.
+    #[inline(always)]
+    pub unsafe fn as_ref(&self) -> &T {
+        unsafe { &*self.ptr }
+    }
+}
+
+impl<T> Drop for HazardRecord<'_, T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.slot.ptr.store(ptr::null_mut(), Ordering::Release);
+        self.slot.active.store(false, Ordering::Release);
+    }
+}
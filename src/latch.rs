@@ -0,0 +1,81 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// A latch blocks threads until its count reaches zero.
+///
+/// Unlike a wait group, it can not be reused: once the count reaches zero
+/// the latch stays open and all later waits return immediately.
+pub struct CountdownLatch {
+    count: AtomicU32,
+    open: AtomicU32,
+}
+
+impl CountdownLatch {
+    #[inline(always)]
+    pub fn new(n: u32) -> Self {
+        Self {
+            count: AtomicU32::new(n),
+            open: AtomicU32::new((n == 0) as u32),
+        }
+    }
+
+    /// Decrements the count and opens the latch when it reaches zero.
+    ///
+    /// Counting down an opened latch is a no-op.
+    pub fn count_down(&self) {
+        let mut count = self.count.load(Ordering::Relaxed);
+        while count > 0 {
+            match self.count.compare_exchange_weak(
+                count,
+                count - 1,
+                Ordering::AcqRel,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    if count == 1 {
+                        self.open.store(1, Ordering::Release);
+                        omango_futex::wake_all(&self.open);
+                    }
+                    return;
+                }
+                Err(current) => count = current,
+            }
+        }
+    }
+
+    /// Blocks the current thread until the latch is opened.
+    pub fn await_zero(&self) {
+        while self.open.load(Ordering::Acquire) == 0 {
+            omango_futex::wait(&self.open, 0);
+        }
+    }
+
+    #[inline(always)]
+    pub fn count(&self) -> u32 {
+        self.count.load(Ordering::Relaxed)
+    }
+
+    #[inline(always)]
+    pub fn is_open(&self) -> bool {
+        self.open.load(Ordering::Acquire) == 1
+    }
+}
@@ -0,0 +1,107 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+
+use crate::reentrant_mutex::current_thread_id;
+
+const FREE: u32 = 0;
+const TAKEN: u32 = 1;
+
+/// An in-process leader election, at most one thread holds the leadership at a time.
+///
+/// The leadership is taken with a CAS on the futex word, the threads awaiting it
+/// are parked and one of them is woken up when the leader relinquishes.
+pub struct LeaderLatch {
+    state: AtomicU32,
+    leader: AtomicUsize,
+    waiters: AtomicU32,
+}
+
+impl LeaderLatch {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            state: AtomicU32::new(FREE),
+            leader: AtomicUsize::new(0),
+            waiters: AtomicU32::new(0),
+        }
+    }
+
+    /// Takes the leadership if nobody holds it.
+    #[inline]
+    pub fn try_become_leader(&self) -> bool {
+        if self.state.compare_exchange(
+            FREE,
+            TAKEN,
+            Ordering::Acquire,
+            Ordering::Relaxed,
+        ).is_err() {
+            return false;
+        }
+        self.leader.store(current_thread_id(), Ordering::Relaxed);
+        true
+    }
+
+    /// Blocks the current thread until it becomes the leader.
+    pub fn await_leadership(&self) {
+        while !self.try_become_leader() {
+            // The waiter must be published before checking the state again,
+            // so that a concurrent "relinquish" either sees it or the futex sees the free state.
+            self.waiters.fetch_add(1, Ordering::SeqCst);
+            omango_futex::wait(&self.state, TAKEN);
+            self.waiters.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Returns `true` if the current thread is the leader.
+    #[inline]
+    pub fn is_leader(&self) -> bool {
+        self.state.load(Ordering::Acquire) == TAKEN
+            && self.leader.load(Ordering::Relaxed) == current_thread_id()
+    }
+
+    /// Gives up the leadership and wakes up one waiting thread.
+    ///
+    /// It is a no-op if the current thread is not the leader.
+    pub fn relinquish(&self) {
+        if !self.is_leader() {
+            return;
+        }
+        self.leader.store(0, Ordering::Relaxed);
+        self.state.store(FREE, Ordering::SeqCst);
+        if self.waiters.load(Ordering::SeqCst) > 0 {
+            omango_futex::wake_one(&self.state);
+        }
+    }
+
+    /// Returns `true` if any thread is the leader.
+    #[inline(always)]
+    pub fn has_leader(&self) -> bool {
+        self.state.load(Ordering::Relaxed) == TAKEN
+    }
+}
+
+impl Default for LeaderLatch {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
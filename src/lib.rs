@@ -0,0 +1,81 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+mod futex;
+mod shim;
+
+pub mod semaphore;
+pub mod barrier;
+pub mod latch;
+pub mod once;
+pub mod mutex;
+pub mod rwlock;
+pub mod condvar;
+pub mod event;
+pub mod cyclic_barrier;
+pub mod exchanger;
+pub mod parker;
+pub mod notify;
+pub mod watch;
+pub mod once_cell;
+pub mod lazy;
+pub mod fair_mutex;
+pub mod reentrant_mutex;
+pub mod sharded_rwlock;
+pub mod seqlock;
+pub mod spin_barrier;
+pub mod mcs_lock;
+pub mod ticket_lock;
+pub mod token_bucket;
+pub mod phaser;
+pub mod atomic_cell;
+pub mod traits;
+pub mod channel;
+pub mod error;
+pub mod promise;
+pub mod gate;
+pub mod throttle;
+pub mod hazard;
+pub mod versioned;
+pub mod adaptive_lock;
+pub mod padded;
+pub mod auto_reset_event;
+pub mod manual_reset_event;
+pub mod segmented_lock;
+pub mod backoff;
+pub mod futex_condvar;
+pub mod work_stealing;
+pub mod rwlatch;
+pub mod monotonic;
+pub mod broadcast_barrier;
+pub mod arc_swap;
+pub mod fair_semaphore;
+pub mod named_mutex;
+pub mod deadlock;
+pub mod blocking_queue;
+pub mod stamped_lock;
+pub mod leader_latch;
+pub mod async_semaphore;
+pub mod async_barrier;
+pub mod async_latch;
+pub mod async_mutex;
+pub mod async_rwlock;
+pub mod async_channel;
+pub mod async_notify;
@@ -0,0 +1,94 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+// The lowest bit tells whether the event is open,
+// the other bits count the sets which opened it.
+const OPEN: u32 = 1;
+const ONE_GENERATION: u32 = 2;
+
+/// An event which stays open once set, until it is explicitly reset.
+///
+/// Setting the event releases all waiting threads,
+/// and the later waits return immediately while it remains open.
+pub struct ManualResetEvent {
+    state: AtomicU32,
+}
+
+impl ManualResetEvent {
+    #[inline(always)]
+    pub const fn new(set: bool) -> Self {
+        Self {
+            state: AtomicU32::new(if set { OPEN } else { 0 }),
+        }
+    }
+
+    /// Opens the event and wakes up all waiting threads.
+    #[inline]
+    pub fn set(&self) {
+        let prev = self.state.fetch_update(Ordering::Release, Ordering::Relaxed, |state| {
+            if state & OPEN == OPEN {
+                return None;
+            }
+            Some(state.wrapping_add(ONE_GENERATION) | OPEN)
+        });
+        if prev.is_ok() {
+            omango_futex::wake_all(&self.state);
+        }
+    }
+
+    /// Closes the event, the next waits block until it is set again.
+    #[inline(always)]
+    pub fn reset(&self) {
+        self.state.fetch_and(!OPEN, Ordering::Relaxed);
+    }
+
+    /// Blocks the current thread while the event is closed.
+    ///
+    /// A `set` which happens while the thread waits releases it,
+    /// even if the event is reset again before the thread wakes up.
+    #[inline]
+    pub fn wait(&self) {
+        let state = self.state.load(Ordering::Acquire);
+        if state & OPEN == OPEN {
+            return;
+        }
+        loop {
+            omango_futex::wait(&self.state, state);
+            // Any change of the closed state comes from a set, the generation has moved on.
+            if self.state.load(Ordering::Acquire) != state {
+                return;
+            }
+        }
+    }
+
+    #[inline(always)]
+    pub fn is_set(&self) -> bool {
+        self.state.load(Ordering::Acquire) & OPEN == OPEN
+    }
+}
+
+impl Default for ManualResetEvent {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(false)
+    }
+}
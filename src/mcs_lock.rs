@@ -0,0 +1,182 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    ptr,
+    sync::atomic::{AtomicBool, AtomicPtr, Ordering},
+};
+
+use crate::backoff::Backoff;
+
+/// A queue-based spinlock where each waiter spins on its own node.
+///
+/// Waiters are linked in arrival order and every one of them spins on the flag
+/// of its own node instead of a shared word, so the lock hand-over only touches
+/// the cache line of the next waiter. The lock is granted in FIFO order.
+pub struct MCSLock {
+    tail: AtomicPtr<MCSNode>,
+}
+
+/// The queue node of a thread acquiring the [`MCSLock`], usually allocated on its stack.
+pub struct MCSNode {
+    next: AtomicPtr<MCSNode>,
+    locked: AtomicBool,
+}
+
+impl MCSNode {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            next: AtomicPtr::new(ptr::null_mut()),
+            locked: AtomicBool::new(false),
+        }
+    }
+}
+
+impl Default for MCSNode {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MCSLock {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self { tail: AtomicPtr::new(ptr::null_mut()) }
+    }
+
+    /// Runs `f` while holding the lock, spins until it is able to acquire it.
+    #[inline]
+    pub fn with_lock<R, F: FnOnce() -> R>(&self, f: F) -> R {
+        let mut node = MCSNode::new();
+        // The guard cannot escape this frame, so it is always dropped before the node.
+        let _guard = unsafe { self.lock(&mut node) };
+        f()
+    }
+
+    /// Runs `f` while holding the lock if it is free, returns `None` otherwise.
+    #[inline]
+    pub fn try_with_lock<R, F: FnOnce() -> R>(&self, f: F) -> Option<R> {
+        let mut node = MCSNode::new();
+        let _guard = unsafe { self.try_lock(&mut node) }?;
+        Some(f())
+    }
+
+    /// Acquires the lock, spins until it is able to do so.
+    ///
+    /// The node stays borrowed until the guard is dropped.
+    ///
+    /// # Safety
+    ///
+    /// The guard must be dropped. The queue keeps a pointer to the node,
+    /// leaking the guard (e.g. with [`std::mem::forget`]) lets the node be
+    /// freed while the next locker still writes to it.
+    pub unsafe fn lock<'a>(&'a self, node: &'a mut MCSNode) -> MCSGuard<'a> {
+        node.next.store(ptr::null_mut(), Ordering::Relaxed);
+        node.locked.store(true, Ordering::Relaxed);
+
+        let node_ptr: *mut MCSNode = node;
+        let prev = self.tail.swap(node_ptr, Ordering::AcqRel);
+        if !prev.is_null() {
+            // The previous node is alive until its owner has handed the lock over to us.
+            unsafe { (*prev).next.store(node_ptr, Ordering::Release) };
+
+            let mut backoff = Backoff::new();
+            while node.locked.load(Ordering::Acquire) {
+                backoff.snooze();
+            }
+        }
+        MCSGuard { parent: self, node }
+    }
+
+    /// Acquires the lock without spinning.
+    ///
+    /// # Safety
+    ///
+    /// The same as [`MCSLock::lock`], the returned guard must be dropped.
+    pub unsafe fn try_lock<'a>(&'a self, node: &'a mut MCSNode) -> Option<MCSGuard<'a>> {
+        node.next.store(ptr::null_mut(), Ordering::Relaxed);
+        node.locked.store(false, Ordering::Relaxed);
+
+        let node_ptr: *mut MCSNode = node;
+        if self.tail.compare_exchange(
+            ptr::null_mut(),
+            node_ptr,
+            Ordering::AcqRel,
+            Ordering::Relaxed,
+        ).is_ok() {
+            return Some(MCSGuard { parent: self, node });
+        }
+        None
+    }
+
+    #[inline(always)]
+    pub fn is_locked(&self) -> bool {
+        !self.tail.load(Ordering::Relaxed).is_null()
+    }
+
+    fn unlock(&self, node: &MCSNode) {
+        let mut next = node.next.load(Ordering::Acquire);
+        if next.is_null() {
+            // No known successor, the lock is released if this node is still the tail.
+            let node_ptr = node as *const MCSNode as *mut MCSNode;
+            if self.tail.compare_exchange(
+                node_ptr,
+                ptr::null_mut(),
+                Ordering::Release,
+                Ordering::Relaxed,
+            ).is_ok() {
+                return;
+            }
+
+            // A successor has swapped the tail but has not linked itself yet.
+            let mut backoff = Backoff::new();
+            loop {
+                next = node.next.load(Ordering::Acquire);
+                if !next.is_null() {
+                    break;
+                }
+                backoff.snooze();
+            }
+        }
+        unsafe { (*next).locked.store(false, Ordering::Release) };
+    }
+}
+
+impl Default for MCSLock {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Releases the [`MCSLock`] on drop, handing it over to the next waiter if any.
+pub struct MCSGuard<'a> {
+    parent: &'a MCSLock,
+    node: &'a MCSNode,
+}
+
+impl Drop for MCSGuard<'_> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.parent.unlock(self.node);
+    }
+}
@@ -0,0 +1,113 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    sync::atomic::{AtomicU32, AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use crate::futex;
+
+/// A counter which only goes up, threads can wait until it reaches a target.
+///
+/// There is no decrement or reset by design, so a value once observed
+/// is a lower bound forever, e.g. for log sequence numbers.
+pub struct MonotonicCounter {
+    value: AtomicU64,
+    // The futex word, bumped on increments while there are waiters.
+    futex: AtomicU32,
+    waiters: AtomicU32,
+}
+
+impl MonotonicCounter {
+    #[inline(always)]
+    pub const fn new(initial: u64) -> Self {
+        Self {
+            value: AtomicU64::new(initial),
+            futex: AtomicU32::new(0),
+            waiters: AtomicU32::new(0),
+        }
+    }
+
+    /// Increments the counter, returns the new value and wakes up the waiting threads.
+    #[inline]
+    pub fn increment(&self) -> u64 {
+        let value = self.value.fetch_add(1, Ordering::SeqCst) + 1;
+        if self.waiters.load(Ordering::SeqCst) > 0 {
+            self.futex.fetch_add(1, Ordering::SeqCst);
+            omango_futex::wake_all(&self.futex);
+        }
+        value
+    }
+
+    #[inline(always)]
+    pub fn read(&self) -> u64 {
+        self.value.load(Ordering::Acquire)
+    }
+
+    /// Blocks the current thread until the counter is at least `target`.
+    pub fn wait_for(&self, target: u64) {
+        if self.read() >= target {
+            return;
+        }
+        // The waiter must be published before checking the value again,
+        // so that a concurrent increment either sees it or the check sees the new value.
+        self.waiters.fetch_add(1, Ordering::SeqCst);
+        loop {
+            let epoch = self.futex.load(Ordering::SeqCst);
+            if self.value.load(Ordering::SeqCst) >= target {
+                break;
+            }
+            omango_futex::wait(&self.futex, epoch);
+        }
+        self.waiters.fetch_sub(1, Ordering::SeqCst);
+    }
+
+    /// Blocks the current thread until the counter is at least `target` or the timeout elapsed.
+    ///
+    /// Returns `false` if the timeout elapsed first.
+    pub fn wait_for_timeout(&self, target: u64, d: Duration) -> bool {
+        if self.read() >= target {
+            return true;
+        }
+        let deadline = Instant::now() + d;
+        self.waiters.fetch_add(1, Ordering::SeqCst);
+        let reached = loop {
+            let epoch = self.futex.load(Ordering::SeqCst);
+            if self.value.load(Ordering::SeqCst) >= target {
+                break true;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                break false;
+            }
+            futex::wait_timeout(&self.futex, epoch, deadline - now);
+        };
+        self.waiters.fetch_sub(1, Ordering::SeqCst);
+        reached
+    }
+}
+
+impl Default for MonotonicCounter {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
@@ -0,0 +1,241 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::UnsafeCell,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicBool, AtomicU32, Ordering},
+};
+
+use omango_util::hint::likely;
+
+use crate::backoff::Backoff;
+#[cfg(debug_assertions)]
+use crate::deadlock;
+
+const UNLOCKED: u32 = 0;
+const LOCKED: u32 = 1;
+const LOCKED_WAITERS: u32 = 2;
+
+/// A blocking mutual exclusion lock.
+///
+/// The contended path spins briefly to avoid the syscall overhead for the short
+/// critical sections, then parks the current thread on the futex.
+///
+/// The mutex is marked as poisoned when a thread panics while holding it,
+/// but the lock can still be acquired. It is up to the callers to check [`Mutex::is_poisoned`].
+pub struct Mutex<T> {
+    state: AtomicU32,
+    poisoned: AtomicBool,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send> Send for Mutex<T> {}
+
+unsafe impl<T: Send> Sync for Mutex<T> {}
+
+impl<T> Mutex<T> {
+    #[inline(always)]
+    pub const fn new(value: T) -> Self {
+        Self {
+            state: AtomicU32::new(UNLOCKED),
+            poisoned: AtomicBool::new(false),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Acquires the lock, blocks the current thread until it is able to do so.
+    #[inline]
+    pub fn lock(&self) -> MutexGuard<'_, T> {
+        self.raw_lock();
+        MutexGuard { parent: self, _marker: PhantomData }
+    }
+
+    /// Acquires the lock without blocking.
+    #[inline]
+    pub fn try_lock(&self) -> Option<MutexGuard<'_, T>> {
+        if self.raw_try_lock() {
+            return Some(MutexGuard { parent: self, _marker: PhantomData });
+        }
+        None
+    }
+
+    /// Returns `true` if a thread panicked while holding the lock.
+    #[inline(always)]
+    pub fn is_poisoned(&self) -> bool {
+        self.poisoned.load(Ordering::Relaxed)
+    }
+
+    #[inline(always)]
+    pub fn clear_poison(&self) {
+        self.poisoned.store(false, Ordering::Relaxed);
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+
+    #[inline]
+    pub(crate) fn raw_lock(&self) {
+        if !likely(self.state.compare_exchange_weak(
+            UNLOCKED,
+            LOCKED,
+            Ordering::Acquire,
+            Ordering::Relaxed,
+        ).is_ok()) {
+            #[cfg(debug_assertions)]
+            deadlock::on_lock_attempt(self.id());
+
+            self.lock_contended();
+        }
+
+        #[cfg(debug_assertions)]
+        deadlock::on_locked(self.id());
+    }
+
+    #[inline]
+    pub(crate) fn raw_try_lock(&self) -> bool {
+        let locked = self.state.compare_exchange(
+            UNLOCKED,
+            LOCKED,
+            Ordering::Acquire,
+            Ordering::Relaxed,
+        ).is_ok();
+
+        #[cfg(debug_assertions)]
+        if locked {
+            deadlock::on_locked(self.id());
+        }
+        locked
+    }
+
+    #[inline]
+    pub(crate) fn raw_unlock(&self) {
+        #[cfg(debug_assertions)]
+        deadlock::on_unlocked(self.id());
+
+        if self.state.swap(UNLOCKED, Ordering::Release) == LOCKED_WAITERS {
+            omango_futex::wake_one(&self.state);
+        }
+    }
+
+    /// Identifies the mutex in the deadlock graph.
+    #[cfg(debug_assertions)]
+    #[inline(always)]
+    fn id(&self) -> usize {
+        self as *const Self as usize
+    }
+
+    #[cold]
+    fn lock_contended(&self) {
+        let mut state = self.spin();
+        if state == UNLOCKED {
+            match self.state.compare_exchange(
+                UNLOCKED,
+                LOCKED,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return,
+                Err(current) => state = current,
+            }
+        }
+
+        loop {
+            // Marks there are waiters, so the owner will wake one up on unlock.
+            // The lock is taken if it was unlocked in the meantime.
+            if state != LOCKED_WAITERS
+                && self.state.swap(LOCKED_WAITERS, Ordering::Acquire) == UNLOCKED {
+                return;
+            }
+            omango_futex::wait(&self.state, LOCKED_WAITERS);
+            state = self.spin();
+        }
+    }
+
+    #[inline]
+    fn spin(&self) -> u32 {
+        let mut backoff = Backoff::new();
+        loop {
+            // Only spins while the lock is held without waiters,
+            // there is no reason to spin when others are already parked.
+            let state = self.state.load(Ordering::Relaxed);
+            if state != LOCKED || backoff.snooze_completed() {
+                return state;
+            }
+        }
+    }
+}
+
+impl<T: Default> Default for Mutex<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+/// Releases the [`Mutex`] on drop.
+pub struct MutexGuard<'a, T> {
+    parent: &'a Mutex<T>,
+    // The guard must be dropped by the thread which locked the mutex.
+    _marker: PhantomData<*const ()>,
+}
+
+unsafe impl<T: Sync> Sync for MutexGuard<'_, T> {}
+
+impl<'a, T> MutexGuard<'a, T> {
+    #[inline(always)]
+    pub(crate) fn mutex(&self) -> &'a Mutex<T> {
+        self.parent
+    }
+}
+
+impl<T> Drop for MutexGuard<'_, T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        if std::thread::panicking() {
+            self.parent.poisoned.store(true, Ordering::Relaxed);
+        }
+        self.parent.raw_unlock();
+    }
+}
+
+impl<T> Deref for MutexGuard<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        unsafe { &*self.parent.value.get() }
+    }
+}
+
+impl<T> DerefMut for MutexGuard<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.parent.value.get() }
+    }
+}
@@ -0,0 +1,205 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::ops::{Deref, DerefMut};
+
+use crate::mutex::{Mutex, MutexGuard};
+
+/// A [`Mutex`] with a name, used to detect lock-order bugs in debug builds.
+///
+/// In debug builds, every thread tracks the names of the mutexes it holds and
+/// a global graph records in which order the names were locked. Locking a name
+/// which is already held, or in an order which closes a cycle in the graph,
+/// panics with the cycle. In release builds, it is a thin wrapper around [`Mutex`].
+pub struct NamedMutex<T> {
+    name: &'static str,
+    mutex: Mutex<T>,
+}
+
+impl<T> NamedMutex<T> {
+    #[inline(always)]
+    pub const fn new(name: &'static str, value: T) -> Self {
+        Self {
+            name,
+            mutex: Mutex::new(value),
+        }
+    }
+
+    /// Acquires the lock, blocks the current thread until it is able to do so.
+    ///
+    /// Panics in debug builds if the lock order could deadlock.
+    #[inline]
+    pub fn lock(&self) -> NamedMutexGuard<'_, T> {
+        #[cfg(debug_assertions)]
+        order::before_lock(self.name);
+
+        let guard = self.mutex.lock();
+
+        #[cfg(debug_assertions)]
+        order::locked(self.name);
+
+        self.wrap(guard)
+    }
+
+    /// Acquires the lock without blocking.
+    ///
+    /// A failed attempt can not deadlock, so only the successful ones are tracked.
+    #[inline]
+    pub fn try_lock(&self) -> Option<NamedMutexGuard<'_, T>> {
+        let guard = self.mutex.try_lock()?;
+
+        #[cfg(debug_assertions)]
+        order::locked(self.name);
+
+        Some(self.wrap(guard))
+    }
+
+    #[inline(always)]
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.mutex.get_mut()
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.mutex.into_inner()
+    }
+
+    #[inline(always)]
+    fn wrap<'a>(&'a self, guard: MutexGuard<'a, T>) -> NamedMutexGuard<'a, T> {
+        NamedMutexGuard {
+            #[cfg(debug_assertions)]
+            name: self.name,
+            guard,
+        }
+    }
+}
+
+/// Releases the [`NamedMutex`] on drop.
+pub struct NamedMutexGuard<'a, T> {
+    #[cfg(debug_assertions)]
+    name: &'static str,
+    guard: MutexGuard<'a, T>,
+}
+
+#[cfg(debug_assertions)]
+impl<T> Drop for NamedMutexGuard<'_, T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        order::unlocked(self.name);
+    }
+}
+
+impl<T> Deref for NamedMutexGuard<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T> DerefMut for NamedMutexGuard<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+#[cfg(debug_assertions)]
+mod order {
+    use std::{
+        cell::RefCell,
+        collections::{HashMap, HashSet},
+    };
+
+    use omango_util::lock::RwSpinlock;
+
+    use crate::lazy::LazyLock;
+
+    type Graph = HashMap<&'static str, HashSet<&'static str>>;
+
+    // The edge "a -> b" means "b" was locked while "a" was held.
+    static GRAPH: LazyLock<RwSpinlock<Graph>> = LazyLock::new(|| RwSpinlock::new(HashMap::new()));
+
+    thread_local! {
+        static HELD: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+    }
+
+    /// Checks the new lock against the held ones, then records the lock order.
+    pub(super) fn before_lock(name: &'static str) {
+        let held = HELD.with(|held| held.borrow().clone());
+        if held.contains(&name) {
+            panic!("mutex \"{}\" is already held by the current thread", name);
+        }
+
+        let mut graph = GRAPH.write();
+        for &prev in &held {
+            if let Some(path) = find_path(&graph, name, prev) {
+                drop(graph);
+                let mut cycle = vec![prev];
+                cycle.extend(path);
+                panic!("lock order cycle detected: {}", cycle.join(" -> "));
+            }
+        }
+        for &prev in &held {
+            graph.entry(prev).or_default().insert(name);
+        }
+    }
+
+    #[inline]
+    pub(super) fn locked(name: &'static str) {
+        HELD.with(|held| held.borrow_mut().push(name));
+    }
+
+    #[inline]
+    pub(super) fn unlocked(name: &'static str) {
+        HELD.with(|held| {
+            let mut held = held.borrow_mut();
+            if let Some(idx) = held.iter().rposition(|&n| n == name) {
+                held.remove(idx);
+            }
+        });
+    }
+
+    /// Returns the names on a path from `from` to `to`, both included.
+    fn find_path(graph: &Graph, from: &'static str, to: &'static str) -> Option<Vec<&'static str>> {
+        let mut visited = HashSet::new();
+        let mut stack = vec![(from, vec![from])];
+        while let Some((node, path)) = stack.pop() {
+            if node == to {
+                return Some(path);
+            }
+            if !visited.insert(node) {
+                continue;
+            }
+            for &next in graph.get(node).into_iter().flatten() {
+                let mut next_path = path.clone();
+                next_path.push(next);
+                stack.push((next, next_path));
+            }
+        }
+        None
+    }
+}
@@ -0,0 +1,83 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Notifies threads about an event, storing permits when nobody is waiting.
+///
+/// A notification sent before `wait` is not lost: the next `wait` consumes
+/// the stored permit and returns immediately.
+pub struct Notify {
+    permits: AtomicU32,
+}
+
+impl Notify {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self { permits: AtomicU32::new(0) }
+    }
+
+    /// Stores one permit and wakes up one waiting thread.
+    #[inline]
+    pub fn notify_one(&self) {
+        let _ = self.permits.fetch_update(
+            Ordering::Release,
+            Ordering::Relaxed,
+            |permits| Some(permits.saturating_add(1)),
+        );
+        omango_futex::wake_one(&self.permits);
+    }
+
+    /// Stores the maximum number of permits and wakes up all waiting threads.
+    #[inline]
+    pub fn notify_all(&self) {
+        self.permits.store(u32::MAX, Ordering::Release);
+        omango_futex::wake_all(&self.permits);
+    }
+
+    /// Consumes one permit, blocks the current thread until it is available.
+    pub fn wait(&self) {
+        loop {
+            if self.try_wait() {
+                return;
+            }
+            omango_futex::wait(&self.permits, 0);
+        }
+    }
+
+    /// Consumes one permit without blocking.
+    ///
+    /// Returns `false` if there is no stored permit.
+    #[inline]
+    pub fn try_wait(&self) -> bool {
+        self.permits.fetch_update(
+            Ordering::Acquire,
+            Ordering::Relaxed,
+            |permits| permits.checked_sub(1),
+        ).is_ok()
+    }
+}
+
+impl Default for Notify {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,184 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::UnsafeCell,
+    convert::Infallible,
+    mem::MaybeUninit,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use omango_util::hint::likely;
+
+const UNINIT: u32 = 0;
+const INITIALIZING: u32 = 1;
+const DONE: u32 = 2;
+
+/// A thread-safe cell which is written only once and stores the result of the initialization.
+///
+/// While one thread runs the initialization, the other threads are parked on the futex.
+pub struct OnceLock<T> {
+    status: AtomicU32,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+unsafe impl<T: Send> Send for OnceLock<T> {}
+
+unsafe impl<T: Send + Sync> Sync for OnceLock<T> {}
+
+impl<T> OnceLock<T> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            status: AtomicU32::new(UNINIT),
+            value: UnsafeCell::new(MaybeUninit::uninit()),
+        }
+    }
+
+    /// Returns the value if it was initialized, without blocking.
+    #[inline]
+    pub fn get(&self) -> Option<&T> {
+        if likely(self.status.load(Ordering::Acquire) == DONE) {
+            return Some(unsafe { self.get_unchecked() });
+        }
+        None
+    }
+
+    /// Returns the value, runs `f` to initialize it if the cell is empty.
+    ///
+    /// Only one thread runs `f`, the other ones block until it completes.
+    /// If `f` panics, the cell stays empty and the next caller will retry.
+    #[inline]
+    pub fn get_or_init<F: FnOnce() -> T>(&self, f: F) -> &T {
+        match self.get_or_try_init(|| Ok::<T, Infallible>(f())) {
+            Ok(value) => value,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Same as "get_or_init", but the cell stays empty if `f` returns an error.
+    ///
+    /// It is shared with [`crate::once_cell::OnceCell`], which exposes it.
+    pub(crate) fn get_or_try_init<F, E>(&self, f: F) -> Result<&T, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+    {
+        if let Some(value) = self.get() {
+            return Ok(value);
+        }
+
+        let mut f = Some(f);
+        loop {
+            match self.status.compare_exchange(
+                UNINIT,
+                INITIALIZING,
+                Ordering::Acquire,
+                Ordering::Acquire,
+            ) {
+                Ok(_) => {
+                    let reset = ResetOnPanic { status: &self.status };
+                    let value = (f.take().unwrap())()?;
+                    unsafe { (*self.value.get()).write(value) };
+                    std::mem::forget(reset);
+
+                    self.status.store(DONE, Ordering::Release);
+                    omango_futex::wake_all(&self.status);
+                    return Ok(unsafe { self.get_unchecked() });
+                }
+                Err(DONE) => return Ok(unsafe { self.get_unchecked() }),
+                Err(_) => {
+                    omango_futex::wait(&self.status, INITIALIZING);
+                }
+            }
+        }
+    }
+
+    /// Stores the value if the cell is empty.
+    ///
+    /// Returns `Err(value)` if the cell was already initialized.
+    pub fn set(&self, value: T) -> Result<(), T> {
+        let mut value = Some(value);
+        self.get_or_init(|| value.take().unwrap());
+        match value {
+            None => Ok(()),
+            Some(value) => Err(value),
+        }
+    }
+
+    #[inline]
+    pub(crate) fn get_mut(&mut self) -> Option<&mut T> {
+        if *self.status.get_mut() == DONE {
+            return Some(unsafe { self.value.get_mut().assume_init_mut() });
+        }
+        None
+    }
+
+    /// Creates a cell which is already initialized.
+    #[inline(always)]
+    pub(crate) const fn with_value(value: T) -> Self {
+        Self {
+            status: AtomicU32::new(DONE),
+            value: UnsafeCell::new(MaybeUninit::new(value)),
+        }
+    }
+
+    /// Consumes the cell, returns the value if it was initialized.
+    #[inline]
+    pub fn into_inner(self) -> Option<T> {
+        let this = std::mem::ManuallyDrop::new(self);
+        if this.status.load(Ordering::Relaxed) == DONE {
+            return Some(unsafe { (*this.value.get()).assume_init_read() });
+        }
+        None
+    }
+
+    #[inline(always)]
+    unsafe fn get_unchecked(&self) -> &T {
+        (*self.value.get()).assume_init_ref()
+    }
+}
+
+impl<T> Default for OnceLock<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Drop for OnceLock<T> {
+    fn drop(&mut self) {
+        if *self.status.get_mut() == DONE {
+            unsafe { self.value.get_mut().assume_init_drop() };
+        }
+    }
+}
+
+/// Puts the cell back to the empty state when the initialization fails or panics.
+struct ResetOnPanic<'a> {
+    status: &'a AtomicU32,
+}
+
+impl Drop for ResetOnPanic<'_> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.status.store(UNINIT, Ordering::Release);
+        omango_futex::wake_all(self.status);
+    }
+}
@@ -0,0 +1,105 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::convert::Infallible;
+
+use crate::once::OnceLock;
+
+/// A thread-safe cell which can be written only once.
+///
+/// While one thread runs the initialization, the other threads are parked on the futex.
+/// If the initialization fails or panics, the cell goes back to the empty state
+/// and the next caller runs its own initialization.
+///
+/// Initializing the cell from inside its own initialization deadlocks.
+pub struct OnceCell<T> {
+    // Runs the init protocol, the cell only adds the fallible initialization.
+    inner: OnceLock<T>,
+}
+
+impl<T> OnceCell<T> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            inner: OnceLock::new(),
+        }
+    }
+
+    /// Returns the value if it was initialized, without blocking.
+    #[inline(always)]
+    pub fn get(&self) -> Option<&T> {
+        self.inner.get()
+    }
+
+    /// Returns the value, runs `f` to initialize it if the cell is empty.
+    #[inline]
+    pub fn get_or_init<F: FnOnce() -> T>(&self, f: F) -> &T {
+        match self.get_or_try_init(|| Ok::<T, Infallible>(f())) {
+            Ok(value) => value,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Returns the value, runs `f` to initialize it if the cell is empty.
+    ///
+    /// If `f` returns an error, the cell stays empty and the error is returned.
+    #[inline(always)]
+    pub fn get_or_try_init<F, E>(&self, f: F) -> Result<&T, E>
+    where
+        F: FnOnce() -> Result<T, E>,
+    {
+        self.inner.get_or_try_init(f)
+    }
+
+    /// Stores the value if the cell is empty.
+    ///
+    /// Returns `Err(value)` if the cell was already initialized.
+    #[inline(always)]
+    pub fn set(&self, value: T) -> Result<(), T> {
+        self.inner.set(value)
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.inner.get_mut()
+    }
+
+    /// Consumes the cell, returns the value if it was initialized.
+    #[inline(always)]
+    pub fn into_inner(self) -> Option<T> {
+        self.inner.into_inner()
+    }
+}
+
+impl<T> Default for OnceCell<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> From<T> for OnceCell<T> {
+    #[inline(always)]
+    fn from(value: T) -> Self {
+        Self {
+            inner: OnceLock::with_value(value),
+        }
+    }
+}
@@ -0,0 +1,95 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    mem,
+    ops::Deref,
+    sync::atomic::{
+        AtomicBool, AtomicI16, AtomicI32, AtomicI64, AtomicI8, AtomicIsize,
+        AtomicU16, AtomicU32, AtomicU64, AtomicU8, AtomicUsize,
+    },
+};
+
+const CACHE_LINE: usize = 64;
+
+/// An atomic which occupies its own 64-byte cache line.
+///
+/// It prevents false sharing in arrays of atomics which are updated
+/// independently by different threads.
+#[repr(align(64))]
+#[derive(Debug, Default)]
+pub struct PaddedAtomic<T> {
+    value: T,
+}
+
+impl<T> PaddedAtomic<T> {
+    #[inline(always)]
+    pub const fn from_atomic(value: T) -> Self {
+        Self { value }
+    }
+
+    /// Returns `true` if the inner type is smaller than a cache line,
+    /// i.e. the padding actually separates it from its neighbours.
+    #[inline(always)]
+    pub const fn is_padding_needed() -> bool {
+        mem::size_of::<T>() < CACHE_LINE
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> Deref for PaddedAtomic<T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.value
+    }
+}
+
+macro_rules! impl_new {
+    ($($atomic:ty => $int:ty),* $(,)?) => {
+        $(
+            impl PaddedAtomic<$atomic> {
+                #[inline(always)]
+                pub const fn new(value: $int) -> Self {
+                    Self::from_atomic(<$atomic>::new(value))
+                }
+            }
+        )*
+    };
+}
+
+impl_new! {
+    AtomicBool => bool,
+    AtomicU8 => u8,
+    AtomicU16 => u16,
+    AtomicU32 => u32,
+    AtomicU64 => u64,
+    AtomicUsize => usize,
+    AtomicI8 => i8,
+    AtomicI16 => i16,
+    AtomicI32 => i32,
+    AtomicI64 => i64,
+    AtomicIsize => isize,
+}
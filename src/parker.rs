@@ -0,0 +1,128 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    marker::PhantomData,
+    sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use crate::futex;
+
+const PARKED: u32 = u32::MAX;
+const EMPTY: u32 = 0;
+const NOTIFIED: u32 = 1;
+
+/// Creates a pair of [`Parker`] and [`Unparker`] sharing the same token.
+#[inline]
+pub fn parking() -> (Parker, Unparker) {
+    let parker = Parker {
+        inner: Arc::new(Inner { state: AtomicU32::new(EMPTY) }),
+        _marker: PhantomData,
+    };
+    let unparker = parker.unparker();
+    (parker, unparker)
+}
+
+struct Inner {
+    state: AtomicU32,
+}
+
+/// Blocks the owner thread until the token is made available by an [`Unparker`].
+///
+/// Many `unpark` calls before `park` coalesce into one token,
+/// the next `park` consumes it and returns immediately.
+pub struct Parker {
+    inner: Arc<Inner>,
+    // Only the owner thread parks, so the parker can be moved but not shared.
+    _marker: PhantomData<*const ()>,
+}
+
+unsafe impl Send for Parker {}
+
+impl Parker {
+    /// Blocks the current thread until the token is available, then consumes it.
+    pub fn park(&self) {
+        let state = &self.inner.state;
+
+        // NOTIFIED => EMPTY, EMPTY => PARKED.
+        if state.fetch_sub(1, Ordering::Acquire) == NOTIFIED {
+            return;
+        }
+        loop {
+            omango_futex::wait(state, PARKED);
+            if state.compare_exchange(
+                NOTIFIED,
+                EMPTY,
+                Ordering::Acquire,
+                Ordering::Acquire,
+            ).is_ok() {
+                return;
+            }
+        }
+    }
+
+    /// Blocks the current thread until the token is available or the timeout elapsed.
+    ///
+    /// Returns `true` if the token was consumed.
+    pub fn park_timeout(&self, d: Duration) -> bool {
+        let state = &self.inner.state;
+        if state.fetch_sub(1, Ordering::Acquire) == NOTIFIED {
+            return true;
+        }
+
+        let deadline = Instant::now() + d;
+        loop {
+            let now = Instant::now();
+            if now >= deadline {
+                break;
+            }
+            futex::wait_timeout(state, PARKED, deadline - now);
+            if state.load(Ordering::Relaxed) == NOTIFIED {
+                break;
+            }
+        }
+        state.swap(EMPTY, Ordering::Acquire) == NOTIFIED
+    }
+
+    /// Returns a new handle which makes the token available.
+    #[inline]
+    pub fn unparker(&self) -> Unparker {
+        Unparker { inner: self.inner.clone() }
+    }
+}
+
+/// Makes the token of the paired [`Parker`] available and wakes it up.
+#[derive(Clone)]
+pub struct Unparker {
+    inner: Arc<Inner>,
+}
+
+impl Unparker {
+    #[inline]
+    pub fn unpark(&self) {
+        if self.inner.state.swap(NOTIFIED, Ordering::Release) == PARKED {
+            omango_futex::wake_one(&self.inner.state);
+        }
+    }
+}
@@ -0,0 +1,190 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+
+const UNARRIVED_MASK: u64 = 0xffff;
+const PARTIES_SHIFT: u64 = 16;
+const PARTIES_MASK: u64 = 0xffff << PARTIES_SHIFT;
+const PHASE_SHIFT: u64 = 32;
+const ONE_PARTY: u64 = 1 << PARTIES_SHIFT;
+const ONE_UNARRIVED: u64 = 1;
+const MAX_PARTIES: u64 = 0xffff;
+
+/// A reusable barrier with a dynamic number of parties, advancing through numbered phases.
+///
+/// Parties can register and deregister at any time. When the last registered party
+/// of the current phase arrives, the phase number is advanced and all waiting parties are released.
+///
+/// The state word packs the phase number (high 32 bits), the registered parties (16 bits)
+/// and the parties which have not arrived yet in the current phase (low 16 bits).
+pub struct Phaser {
+    state: AtomicU64,
+    // Bumped after every advance, the waiting parties are parked on it.
+    // The phase itself is only read from the state.
+    futex: AtomicU32,
+    next_id: AtomicUsize,
+}
+
+impl Phaser {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            state: AtomicU64::new(0),
+            futex: AtomicU32::new(0),
+            next_id: AtomicUsize::new(0),
+        }
+    }
+
+    /// Registers a new party for the current phase and returns its identifier.
+    ///
+    /// Panics if there are already 65535 registered parties.
+    pub fn register(&self) -> usize {
+        let mut state = self.state.load(Ordering::Relaxed);
+        loop {
+            assert!(parties(state) < MAX_PARTIES, "too many parties registered on Phaser");
+            match self.state.compare_exchange_weak(
+                state,
+                state + ONE_PARTY + ONE_UNARRIVED,
+                Ordering::AcqRel,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return self.next_id.fetch_add(1, Ordering::Relaxed),
+                Err(current) => state = current,
+            }
+        }
+    }
+
+    /// Marks the arrival of the current thread without waiting for the others.
+    ///
+    /// Returns the phase number the party arrived at.
+    #[inline]
+    pub fn arrive(&self) -> u32 {
+        self.do_arrive(false).0
+    }
+
+    /// Marks the arrival of the current thread and blocks until all parties have arrived.
+    ///
+    /// Returns the new phase number.
+    pub fn arrive_and_await_advance(&self) -> u32 {
+        let (phase, advanced) = self.do_arrive(false);
+        if !advanced {
+            loop {
+                // The sequence must be loaded before checking the phase,
+                // an advance after the check changes it and the wait returns.
+                let seq = self.futex.load(Ordering::Acquire);
+                if self.phase() != phase {
+                    break;
+                }
+                omango_futex::wait(&self.futex, seq);
+            }
+        }
+        phase.wrapping_add(1)
+    }
+
+    /// Marks the arrival of the current thread and removes it from the next phases.
+    ///
+    /// Returns the phase number the party arrived at.
+    #[inline]
+    pub fn arrive_and_deregister(&self) -> u32 {
+        self.do_arrive(true).0
+    }
+
+    #[inline(always)]
+    pub fn phase(&self) -> u32 {
+        phase(self.state.load(Ordering::Acquire))
+    }
+
+    #[inline(always)]
+    pub fn registered(&self) -> u32 {
+        parties(self.state.load(Ordering::Relaxed)) as u32
+    }
+
+    #[inline(always)]
+    pub fn arrived(&self) -> u32 {
+        let state = self.state.load(Ordering::Relaxed);
+        (parties(state) - unarrived(state)) as u32
+    }
+
+    #[inline(always)]
+    pub fn unarrived(&self) -> u32 {
+        unarrived(self.state.load(Ordering::Relaxed)) as u32
+    }
+
+    /// Returns the phase the party arrived at and whether it advanced the phase.
+    fn do_arrive(&self, deregister: bool) -> (u32, bool) {
+        let mut state = self.state.load(Ordering::Relaxed);
+        loop {
+            assert!(unarrived(state) > 0, "arrive on Phaser without registered parties");
+
+            let mut next = state - ONE_UNARRIVED;
+            if deregister {
+                next -= ONE_PARTY;
+            }
+
+            // The last arriving party advances the phase
+            // and resets the unarrived count to the registered parties.
+            let advanced = unarrived(next) == 0;
+            if advanced {
+                next = ((phase(next).wrapping_add(1) as u64) << PHASE_SHIFT)
+                    | (next & PARTIES_MASK)
+                    | parties(next);
+            }
+
+            match self.state.compare_exchange_weak(
+                state,
+                next,
+                Ordering::AcqRel,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    if advanced {
+                        self.futex.fetch_add(1, Ordering::Release);
+                        omango_futex::wake_all(&self.futex);
+                    }
+                    return (phase(state), advanced);
+                }
+                Err(current) => state = current,
+            }
+        }
+    }
+}
+
+impl Default for Phaser {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[inline(always)]
+fn phase(state: u64) -> u32 {
+    (state >> PHASE_SHIFT) as u32
+}
+
+#[inline(always)]
+fn parties(state: u64) -> u64 {
+    (state & PARTIES_MASK) >> PARTIES_SHIFT
+}
+
+#[inline(always)]
+fn unarrived(state: u64) -> u64 {
+    state & UNARRIVED_MASK
+}
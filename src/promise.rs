@@ -0,0 +1,142 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    },
+};
+
+use crate::error::{Error, ErrorKind};
+
+const PENDING: u32 = 0;
+const READY: u32 = 1;
+const TAKEN: u32 = 2;
+
+/// Creates a one-shot pair, the [`Promise`] produces the result
+/// and the [`SyncFuture`] consumes it.
+#[inline]
+pub fn promise<T: Send>() -> (Promise<T>, SyncFuture<T>) {
+    let shared = Arc::new(Shared {
+        status: AtomicU32::new(PENDING),
+        value: UnsafeCell::new(MaybeUninit::uninit()),
+    });
+    (
+        Promise {
+            shared: shared.clone(),
+        },
+        SyncFuture { shared },
+    )
+}
+
+struct Shared<T> {
+    status: AtomicU32,
+    value: UnsafeCell<MaybeUninit<Result<T, Error>>>,
+}
+
+unsafe impl<T: Send> Send for Shared<T> {}
+
+unsafe impl<T: Send> Sync for Shared<T> {}
+
+impl<T> Drop for Shared<T> {
+    fn drop(&mut self) {
+        if *self.status.get_mut() == READY {
+            unsafe { self.value.get_mut().assume_init_drop() };
+        }
+    }
+}
+
+/// The producing side, it delivers either a value or an error exactly once.
+///
+/// Dropping it without delivering anything rejects the future.
+pub struct Promise<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T: Send> Promise<T> {
+    /// Delivers the value and wakes up the waiting future.
+    #[inline]
+    pub fn resolve(self, value: T) {
+        self.complete(Ok(value));
+    }
+
+    /// Delivers the error and wakes up the waiting future.
+    #[inline]
+    pub fn reject(self, err: Error) {
+        self.complete(Err(err));
+    }
+}
+
+impl<T> Promise<T> {
+    fn complete(&self, result: Result<T, Error>) {
+        // The promise is the only writer, so the slot is free while the status is pending.
+        unsafe { (*self.shared.value.get()).write(result) };
+        self.shared.status.store(READY, Ordering::Release);
+        omango_futex::wake_all(&self.shared.status);
+    }
+}
+
+impl<T> Drop for Promise<T> {
+    fn drop(&mut self) {
+        if self.shared.status.load(Ordering::Relaxed) == PENDING {
+            self.complete(Err(Error::new(ErrorKind::Cancelled, "promise dropped")));
+        }
+    }
+}
+
+/// The consuming side, it receives the result delivered by the [`Promise`].
+pub struct SyncFuture<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T: Send> SyncFuture<T> {
+    /// Blocks the current thread until the promise is resolved, rejected or dropped.
+    pub fn get(self) -> Result<T, Error> {
+        loop {
+            if let Some(result) = self.try_get() {
+                return result;
+            }
+            if self.shared.status.load(Ordering::Relaxed) == TAKEN {
+                return Err(Error::new(ErrorKind::UserError, "result already taken"));
+            }
+            omango_futex::wait(&self.shared.status, PENDING);
+        }
+    }
+
+    /// Returns the result if it was delivered, without blocking.
+    ///
+    /// The result is moved out, so the next calls return `None`.
+    pub fn try_get(&self) -> Option<Result<T, Error>> {
+        // Only one caller may move the result out.
+        self.shared
+            .status
+            .compare_exchange(READY, TAKEN, Ordering::Acquire, Ordering::Relaxed)
+            .ok()?;
+        Some(unsafe { (*self.shared.value.get()).assume_init_read() })
+    }
+
+    #[inline(always)]
+    pub fn is_ready(&self) -> bool {
+        self.shared.status.load(Ordering::Acquire) == READY
+    }
+}
@@ -0,0 +1,158 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    marker::PhantomData,
+    ops::Deref,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::mutex::Mutex;
+
+/// A mutual exclusion lock which can be locked many times by the thread holding it.
+///
+/// The lock is released only when the outermost guard is dropped,
+/// other threads block as with the normal [`Mutex`].
+///
+/// The guards give the shared access only, because many of them can exist in the same thread.
+pub struct ReentrantMutex<T> {
+    mutex: Mutex<()>,
+    owner: AtomicUsize,
+    depth: AtomicUsize,
+    value: T,
+}
+
+unsafe impl<T: Send> Send for ReentrantMutex<T> {}
+
+unsafe impl<T: Send> Sync for ReentrantMutex<T> {}
+
+impl<T> ReentrantMutex<T> {
+    #[inline(always)]
+    pub const fn new(value: T) -> Self {
+        Self {
+            mutex: Mutex::new(()),
+            owner: AtomicUsize::new(0),
+            depth: AtomicUsize::new(0),
+            value,
+        }
+    }
+
+    /// Acquires the lock, blocks the current thread until it is able to do so.
+    ///
+    /// Returns immediately if the current thread already holds the lock.
+    pub fn lock(&self) -> ReentrantMutexGuard<'_, T> {
+        let id = current_thread_id();
+        if self.owner.load(Ordering::Relaxed) == id {
+            self.increase_depth();
+        } else {
+            self.mutex.raw_lock();
+            self.set_owner(id);
+        }
+        ReentrantMutexGuard { parent: self, _marker: PhantomData }
+    }
+
+    /// Acquires the lock without blocking.
+    pub fn try_lock(&self) -> Option<ReentrantMutexGuard<'_, T>> {
+        let id = current_thread_id();
+        if self.owner.load(Ordering::Relaxed) == id {
+            self.increase_depth();
+        } else if self.mutex.raw_try_lock() {
+            self.set_owner(id);
+        } else {
+            return None;
+        }
+        Some(ReentrantMutexGuard { parent: self, _marker: PhantomData })
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+
+    #[inline(always)]
+    fn set_owner(&self, id: usize) {
+        self.owner.store(id, Ordering::Relaxed);
+        self.depth.store(1, Ordering::Relaxed);
+    }
+
+    #[inline(always)]
+    fn increase_depth(&self) {
+        // Only the owner thread touches the depth.
+        let depth = self.depth.load(Ordering::Relaxed);
+        self.depth.store(
+            depth.checked_add(1).expect("lock count overflow in reentrant mutex"),
+            Ordering::Relaxed,
+        );
+    }
+
+    #[inline]
+    fn unlock(&self) {
+        let depth = self.depth.load(Ordering::Relaxed) - 1;
+        self.depth.store(depth, Ordering::Relaxed);
+        if depth == 0 {
+            self.owner.store(0, Ordering::Relaxed);
+            self.mutex.raw_unlock();
+        }
+    }
+}
+
+impl<T: Default> Default for ReentrantMutex<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+/// Returns an identifier which is unique among the running threads and never zero.
+#[inline(always)]
+pub(crate) fn current_thread_id() -> usize {
+    thread_local! {
+        static ID: u8 = const { 0 };
+    }
+    ID.with(|id| id as *const u8 as usize)
+}
+
+/// Releases one level of the [`ReentrantMutex`] on drop.
+pub struct ReentrantMutexGuard<'a, T> {
+    parent: &'a ReentrantMutex<T>,
+    // The guard must be dropped by the thread which owns the lock.
+    _marker: PhantomData<*const ()>,
+}
+
+impl<T> Drop for ReentrantMutexGuard<'_, T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.parent.unlock();
+    }
+}
+
+impl<T> Deref for ReentrantMutexGuard<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        &self.parent.value
+    }
+}
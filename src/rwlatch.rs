@@ -0,0 +1,146 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
+
+const READ: u32 = 0;
+const DRAINING: u32 = 1;
+const WRITE: u32 = 2;
+
+/// A latch which alternates between a read phase shared by many threads
+/// and a write phase owned by a single thread, as used for page latches.
+///
+/// [`ReadWriteLatch::flip_to_write`] blocks the new readers and waits
+/// for the current ones to exit before the writer is let in.
+pub struct ReadWriteLatch {
+    readers: AtomicUsize,
+    write_intent: AtomicBool,
+    // The futex word of the phase, readers and writers wait on it for their phase.
+    phase: AtomicU32,
+    // Bumped by the last reader leaving while a flip waits for the readers to drain.
+    drained: AtomicU32,
+    writer: AtomicU32,
+}
+
+impl ReadWriteLatch {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            readers: AtomicUsize::new(0),
+            write_intent: AtomicBool::new(false),
+            phase: AtomicU32::new(READ),
+            drained: AtomicU32::new(0),
+            writer: AtomicU32::new(0),
+        }
+    }
+
+    /// Enters the read phase, blocks while the latch is flipping to or in the write phase.
+    pub fn read_enter(&self) {
+        loop {
+            let phase = self.phase.load(Ordering::Acquire);
+            if phase != READ {
+                omango_futex::wait(&self.phase, phase);
+                continue;
+            }
+
+            // The reader must be published before checking the intent,
+            // so that a concurrent flip either sees it or the reader sees the flip.
+            self.readers.fetch_add(1, Ordering::SeqCst);
+            if !self.write_intent.load(Ordering::SeqCst) {
+                return;
+            }
+            self.read_exit();
+        }
+    }
+
+    /// Leaves the read phase, the last reader lets a pending flip complete.
+    #[inline]
+    pub fn read_exit(&self) {
+        if self.readers.fetch_sub(1, Ordering::SeqCst) == 1
+            && self.write_intent.load(Ordering::SeqCst) {
+            self.drained.fetch_add(1, Ordering::SeqCst);
+            omango_futex::wake_one(&self.drained);
+        }
+    }
+
+    /// Flips the latch to the write phase.
+    ///
+    /// The new readers are blocked, then the call waits for the current readers to exit.
+    pub fn flip_to_write(&self) {
+        self.write_intent.store(true, Ordering::SeqCst);
+        self.phase.store(DRAINING, Ordering::SeqCst);
+        loop {
+            let drained = self.drained.load(Ordering::SeqCst);
+            if self.readers.load(Ordering::SeqCst) == 0 {
+                break;
+            }
+            omango_futex::wait(&self.drained, drained);
+        }
+        self.phase.store(WRITE, Ordering::Release);
+        omango_futex::wake_all(&self.phase);
+    }
+
+    /// Flips the latch back to the read phase and releases the blocked readers.
+    #[inline]
+    pub fn flip_to_read(&self) {
+        self.write_intent.store(false, Ordering::SeqCst);
+        self.phase.store(READ, Ordering::Release);
+        omango_futex::wake_all(&self.phase);
+    }
+
+    /// Enters the write phase, blocks until the latch is flipped to it
+    /// and no other thread holds it.
+    pub fn write_enter(&self) {
+        loop {
+            let phase = self.phase.load(Ordering::Acquire);
+            if phase == WRITE {
+                break;
+            }
+            omango_futex::wait(&self.phase, phase);
+        }
+        while self.writer.swap(1, Ordering::Acquire) != 0 {
+            omango_futex::wait(&self.writer, 1);
+        }
+    }
+
+    /// Leaves the write phase and lets the next writer in.
+    #[inline]
+    pub fn write_exit(&self) {
+        self.writer.store(0, Ordering::Release);
+        omango_futex::wake_one(&self.writer);
+    }
+
+    #[inline(always)]
+    pub fn is_write_phase(&self) -> bool {
+        self.phase.load(Ordering::Acquire) == WRITE
+    }
+
+    #[inline(always)]
+    pub fn readers(&self) -> usize {
+        self.readers.load(Ordering::Relaxed)
+    }
+}
+
+impl Default for ReadWriteLatch {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
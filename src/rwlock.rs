@@ -0,0 +1,418 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::UnsafeCell,
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use omango_util::hint::likely;
+
+use crate::backoff::Backoff;
+
+const READ_LOCKED: u32 = 1;
+const MASK: u32 = (1 << 30) - 1;
+const WRITE_LOCKED: u32 = MASK;
+const MAX_READERS: u32 = MASK - 1;
+const READERS_WAITING: u32 = 1 << 30;
+const WRITERS_WAITING: u32 = 1 << 31;
+
+/// A blocking reader-writer lock.
+///
+/// The low 30 bits of the state word count the readers (all of them set means write-locked),
+/// the two high bits mark the parked readers and writers.
+///
+/// New readers are blocked as soon as a writer is waiting,
+/// so the writers are not starved under the heavy read load.
+pub struct RwLock<T> {
+    state: AtomicU32,
+    writer_notify: AtomicU32,
+    writers_parked: AtomicU32,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send> Send for RwLock<T> {}
+
+unsafe impl<T: Send + Sync> Sync for RwLock<T> {}
+
+impl<T> RwLock<T> {
+    #[inline(always)]
+    pub const fn new(value: T) -> Self {
+        Self {
+            state: AtomicU32::new(0),
+            writer_notify: AtomicU32::new(0),
+            writers_parked: AtomicU32::new(0),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Acquires the shared access, blocks the current thread until it is able to do so.
+    #[inline]
+    pub fn read(&self) -> RwLockReadGuard<'_, T> {
+        let state = self.state.load(Ordering::Relaxed);
+        if !is_read_lockable(state) || self.state.compare_exchange_weak(
+            state,
+            state + READ_LOCKED,
+            Ordering::Acquire,
+            Ordering::Relaxed,
+        ).is_err() {
+            self.read_contended();
+        }
+        RwLockReadGuard { parent: self }
+    }
+
+    /// Acquires the shared access without blocking.
+    #[inline]
+    pub fn try_read(&self) -> Option<RwLockReadGuard<'_, T>> {
+        let mut state = self.state.load(Ordering::Relaxed);
+        while is_read_lockable(state) {
+            match self.state.compare_exchange_weak(
+                state,
+                state + READ_LOCKED,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return Some(RwLockReadGuard { parent: self }),
+                Err(current) => state = current,
+            }
+        }
+        None
+    }
+
+    /// Acquires the exclusive access, blocks the current thread until it is able to do so.
+    #[inline]
+    pub fn write(&self) -> RwLockWriteGuard<'_, T> {
+        if self.state.compare_exchange_weak(
+            0,
+            WRITE_LOCKED,
+            Ordering::Acquire,
+            Ordering::Relaxed,
+        ).is_err() {
+            self.write_contended();
+        }
+        RwLockWriteGuard { parent: self }
+    }
+
+    /// Acquires the exclusive access without blocking.
+    #[inline]
+    pub fn try_write(&self) -> Option<RwLockWriteGuard<'_, T>> {
+        let mut state = self.state.load(Ordering::Relaxed);
+        while is_unlocked(state) {
+            match self.state.compare_exchange_weak(
+                state,
+                state + WRITE_LOCKED,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return Some(RwLockWriteGuard { parent: self }),
+                Err(current) => state = current,
+            }
+        }
+        None
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+
+    #[cold]
+    fn read_contended(&self) {
+        let mut state = self.spin_read();
+        loop {
+            if is_read_lockable(state) {
+                match self.state.compare_exchange_weak(
+                    state,
+                    state + READ_LOCKED,
+                    Ordering::Acquire,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => return,
+                    Err(current) => {
+                        state = current;
+                        continue;
+                    }
+                }
+            }
+
+            if state & MASK == MAX_READERS {
+                panic!("too many active read locks on RwLock");
+            }
+
+            // The readers waiting bit must be set before going to sleep.
+            if !has_readers_waiting(state) {
+                if let Err(current) = self.state.compare_exchange(
+                    state,
+                    state | READERS_WAITING,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    state = current;
+                    continue;
+                }
+            }
+
+            omango_futex::wait(&self.state, state | READERS_WAITING);
+            state = self.spin_read();
+        }
+    }
+
+    #[cold]
+    fn write_contended(&self) {
+        let mut state = self.spin_write();
+
+        // Once this thread has been parked, it can not know whether other writers are parked too,
+        // so the bit is kept set conservatively when the lock is acquired.
+        let mut other_writers_waiting = 0;
+        loop {
+            if is_unlocked(state) {
+                match self.state.compare_exchange_weak(
+                    state,
+                    state | WRITE_LOCKED | other_writers_waiting,
+                    Ordering::Acquire,
+                    Ordering::Relaxed,
+                ) {
+                    Ok(_) => return,
+                    Err(current) => {
+                        state = current;
+                        continue;
+                    }
+                }
+            }
+
+            // The writers waiting bit must be set before going to sleep.
+            if !has_writers_waiting(state) {
+                if let Err(current) = self.state.compare_exchange(
+                    state,
+                    state | WRITERS_WAITING,
+                    Ordering::Relaxed,
+                    Ordering::Relaxed,
+                ) {
+                    state = current;
+                    continue;
+                }
+            }
+            other_writers_waiting = WRITERS_WAITING;
+
+            // The notification counter is read before checking the state again,
+            // so a wake up between the check and the sleep is not missed.
+            let seq = self.writer_notify.load(Ordering::Acquire);
+            state = self.state.load(Ordering::Relaxed);
+            if is_unlocked(state) || !has_writers_waiting(state) {
+                continue;
+            }
+
+            self.writers_parked.fetch_add(1, Ordering::SeqCst);
+            omango_futex::wait(&self.writer_notify, seq);
+            self.writers_parked.fetch_sub(1, Ordering::SeqCst);
+            state = self.spin_write();
+        }
+    }
+
+    #[inline]
+    fn read_unlock(&self) {
+        let state = self.state.fetch_sub(READ_LOCKED, Ordering::Release) - READ_LOCKED;
+
+        // Readers can only be parked on a read-locked lock when a writer is waiting too.
+        if is_unlocked(state) && has_writers_waiting(state) {
+            self.wake_writer_or_readers(state);
+        }
+    }
+
+    #[inline]
+    fn write_unlock(&self) {
+        let state = self.state.fetch_sub(WRITE_LOCKED, Ordering::Release) - WRITE_LOCKED;
+        if has_readers_waiting(state) || has_writers_waiting(state) {
+            self.wake_writer_or_readers(state);
+        }
+    }
+
+    #[cold]
+    fn wake_writer_or_readers(&self, mut state: u32) {
+        debug_assert!(is_unlocked(state));
+
+        // Only writers are waiting.
+        if state == WRITERS_WAITING {
+            match self.state.compare_exchange(
+                state,
+                0,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => {
+                    self.wake_writer();
+                    return;
+                }
+                Err(current) => state = current,
+            }
+        }
+
+        // Both are waiting, the writer goes first and the readers will be woken up when it unlocks.
+        // The writers waiting bit may be stale since writers keep it set conservatively,
+        // if no writer was parked, the readers are woken up instead.
+        if state == READERS_WAITING + WRITERS_WAITING {
+            if self.state.compare_exchange(
+                state,
+                READERS_WAITING,
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+            ).is_err() {
+                return;
+            }
+            if self.wake_writer() {
+                return;
+            }
+            state = READERS_WAITING;
+        }
+
+        // Only readers are waiting, all of them are woken up.
+        if state == READERS_WAITING && self.state.compare_exchange(
+            state,
+            0,
+            Ordering::Relaxed,
+            Ordering::Relaxed,
+        ).is_ok() {
+            omango_futex::wake_all(&self.state);
+        }
+    }
+
+    /// Returns `false` if there was no parked writer.
+    #[inline]
+    fn wake_writer(&self) -> bool {
+        self.writer_notify.fetch_add(1, Ordering::SeqCst);
+        if self.writers_parked.load(Ordering::SeqCst) == 0 {
+            return false;
+        }
+        omango_futex::wake_one(&self.writer_notify);
+        true
+    }
+
+    #[inline]
+    fn spin_read(&self) -> u32 {
+        self.spin_until(|state| {
+            !is_write_locked(state) || has_readers_waiting(state) || has_writers_waiting(state)
+        })
+    }
+
+    #[inline]
+    fn spin_write(&self) -> u32 {
+        self.spin_until(|state| is_unlocked(state) || has_writers_waiting(state))
+    }
+
+    #[inline]
+    fn spin_until<F: Fn(u32) -> bool>(&self, f: F) -> u32 {
+        let mut backoff = Backoff::new();
+        loop {
+            let state = self.state.load(Ordering::Relaxed);
+            if f(state) || backoff.snooze_completed() {
+                return state;
+            }
+        }
+    }
+}
+
+impl<T: Default> Default for RwLock<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+#[inline(always)]
+fn is_unlocked(state: u32) -> bool {
+    state & MASK == 0
+}
+
+#[inline(always)]
+fn is_write_locked(state: u32) -> bool {
+    state & MASK == WRITE_LOCKED
+}
+
+#[inline(always)]
+fn has_readers_waiting(state: u32) -> bool {
+    state & READERS_WAITING != 0
+}
+
+#[inline(always)]
+fn has_writers_waiting(state: u32) -> bool {
+    state & WRITERS_WAITING != 0
+}
+
+#[inline(always)]
+fn is_read_lockable(state: u32) -> bool {
+    // Readers are not allowed to join while others are waiting,
+    // it prevents the writers from starving.
+    likely(state & MASK < MAX_READERS) && !has_readers_waiting(state) && !has_writers_waiting(state)
+}
+
+/// Releases the shared access of the [`RwLock`] on drop.
+pub struct RwLockReadGuard<'a, T> {
+    parent: &'a RwLock<T>,
+}
+
+impl<T> Drop for RwLockReadGuard<'_, T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.parent.read_unlock();
+    }
+}
+
+impl<T> Deref for RwLockReadGuard<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        unsafe { &*self.parent.value.get() }
+    }
+}
+
+/// Releases the exclusive access of the [`RwLock`] on drop.
+pub struct RwLockWriteGuard<'a, T> {
+    parent: &'a RwLock<T>,
+}
+
+impl<T> Drop for RwLockWriteGuard<'_, T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.parent.write_unlock();
+    }
+}
+
+impl<T> Deref for RwLockWriteGuard<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        unsafe { &*self.parent.value.get() }
+    }
+}
+
+impl<T> DerefMut for RwLockWriteGuard<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.parent.value.get() }
+    }
+}
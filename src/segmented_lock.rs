@@ -0,0 +1,130 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use crate::{
+    mutex::{Mutex, MutexGuard},
+    rwlock::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+#[inline]
+fn segment_of<K: Hash + ?Sized>(key: &K, n: usize) -> usize {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    hasher.finish() as usize % n
+}
+
+/// An array of `N` mutexes which serializes the work per key instead of globally.
+///
+/// Keys hashed to different segments are locked concurrently,
+/// only the keys colliding on the same segment wait for each other.
+pub struct SegmentedLock<const N: usize = 64> {
+    segments: [Mutex<()>; N],
+}
+
+impl<const N: usize> SegmentedLock<N> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        assert!(N > 0, "SegmentedLock must have at least one segment");
+        Self {
+            segments: [const { Mutex::new(()) }; N],
+        }
+    }
+
+    /// Locks the segment which the key is hashed to.
+    #[inline]
+    pub fn lock_for<K: Hash + ?Sized>(&self, key: &K) -> MutexGuard<'_, ()> {
+        self.segments[segment_of(key, N)].lock()
+    }
+
+    /// Locks the segment which the key is hashed to, without blocking.
+    #[inline]
+    pub fn try_lock_for<K: Hash + ?Sized>(&self, key: &K) -> Option<MutexGuard<'_, ()>> {
+        self.segments[segment_of(key, N)].try_lock()
+    }
+
+    /// Returns the index of the segment which the key is hashed to.
+    #[inline(always)]
+    pub fn segment_index<K: Hash + ?Sized>(&self, key: &K) -> usize {
+        segment_of(key, N)
+    }
+
+    #[inline(always)]
+    pub const fn segment_count(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> Default for SegmentedLock<N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The reader-writer variant of [`SegmentedLock`],
+/// readers of the same segment do not wait for each other.
+pub struct SegmentedRwLock<const N: usize = 64> {
+    segments: [RwLock<()>; N],
+}
+
+impl<const N: usize> SegmentedRwLock<N> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        assert!(N > 0, "SegmentedRwLock must have at least one segment");
+        Self {
+            segments: [const { RwLock::new(()) }; N],
+        }
+    }
+
+    /// Acquires the shared access of the segment which the key is hashed to.
+    #[inline]
+    pub fn read_for<K: Hash + ?Sized>(&self, key: &K) -> RwLockReadGuard<'_, ()> {
+        self.segments[segment_of(key, N)].read()
+    }
+
+    /// Acquires the exclusive access of the segment which the key is hashed to.
+    #[inline]
+    pub fn write_for<K: Hash + ?Sized>(&self, key: &K) -> RwLockWriteGuard<'_, ()> {
+        self.segments[segment_of(key, N)].write()
+    }
+
+    /// Returns the index of the segment which the key is hashed to.
+    #[inline(always)]
+    pub fn segment_index<K: Hash + ?Sized>(&self, key: &K) -> usize {
+        segment_of(key, N)
+    }
+
+    #[inline(always)]
+    pub const fn segment_count(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> Default for SegmentedRwLock<N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
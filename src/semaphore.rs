@@ -0,0 +1,118 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use omango_util::hint::likely;
+
+use crate::backoff::Backoff;
+
+/// A counting semaphore limiting the number of threads
+/// which access a shared resource at the same time.
+///
+/// Threads which can not get a permit are parked on the futex
+/// until another thread releases one.
+pub struct Semaphore {
+    // The futex needs a 32-bit unsigned word, and the permits never go below zero:
+    // an acquire only takes one when the count is positive.
+    permits: AtomicU32,
+    waiters: AtomicU32,
+}
+
+impl Semaphore {
+    #[inline(always)]
+    pub fn new(permits: u32) -> Self {
+        Self {
+            permits: AtomicU32::new(permits),
+            waiters: AtomicU32::new(0),
+        }
+    }
+
+    /// Takes one permit, blocks the current thread until a permit is available.
+    pub fn acquire(&self) {
+        let mut backoff = Backoff::new();
+        loop {
+            if likely(self.try_acquire()) {
+                return;
+            }
+            if !backoff.snooze_completed() {
+                continue;
+            }
+
+            // The waiter must be published before checking the permits again,
+            // so that a concurrent "release" either sees it or the futex sees the new permit.
+            self.waiters.fetch_add(1, Ordering::SeqCst);
+            omango_futex::wait(&self.permits, 0);
+            self.waiters.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Takes one permit and returns a guard which gives it back on drop.
+    #[inline]
+    pub fn acquire_guard(&self) -> SemaphoreGuard<'_> {
+        self.acquire();
+        SemaphoreGuard { parent: self }
+    }
+
+    /// Takes one permit without blocking.
+    ///
+    /// Returns `false` if there is no available permit.
+    pub fn try_acquire(&self) -> bool {
+        let mut permits = self.permits.load(Ordering::Relaxed);
+        while permits > 0 {
+            match self.permits.compare_exchange_weak(
+                permits,
+                permits - 1,
+                Ordering::Acquire,
+                Ordering::Relaxed,
+            ) {
+                Ok(_) => return true,
+                Err(current) => permits = current,
+            }
+        }
+        false
+    }
+
+    /// Gives back one permit and wakes up one waiting thread if any.
+    #[inline]
+    pub fn release(&self) {
+        self.permits.fetch_add(1, Ordering::SeqCst);
+        if self.waiters.load(Ordering::SeqCst) > 0 {
+            omango_futex::wake_one(&self.permits);
+        }
+    }
+
+    #[inline(always)]
+    pub fn available_permits(&self) -> u32 {
+        self.permits.load(Ordering::Relaxed)
+    }
+}
+
+/// Gives back the permit to the [`Semaphore`] on drop.
+pub struct SemaphoreGuard<'a> {
+    parent: &'a Semaphore,
+}
+
+impl Drop for SemaphoreGuard<'_> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.parent.release();
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::UnsafeCell,
+    mem::MaybeUninit,
+    ptr,
+    sync::atomic::{fence, AtomicU64, Ordering},
+};
+
+use omango_util::lock::RwSpinlock;
+
+use crate::backoff::Backoff;
+
+/// A sequence lock for the read-heavy data which is cheap to copy.
+///
+/// Readers never block writers: they copy the value and retry if the sequence
+/// changed meanwhile. An odd sequence means a write is in progress.
+///
+/// `T` must be `Copy` because a reader may copy a torn value before detecting
+/// the concurrent write and discarding it. Concurrent writers are serialized by a spinlock.
+pub struct SeqLock<T: Copy> {
+    seq: AtomicU64,
+    writer: RwSpinlock<()>,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: Copy + Send> Send for SeqLock<T> {}
+
+unsafe impl<T: Copy + Send> Sync for SeqLock<T> {}
+
+impl<T: Copy> SeqLock<T> {
+    #[inline(always)]
+    pub fn new(value: T) -> Self {
+        Self {
+            seq: AtomicU64::new(0),
+            writer: RwSpinlock::new(()),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Returns a consistent copy of the value, retries while writers are active.
+    pub fn read(&self) -> T {
+        let mut backoff = Backoff::new();
+        loop {
+            if let Some(value) = self.try_read() {
+                return value;
+            }
+            backoff.snooze();
+        }
+    }
+
+    /// Returns a copy of the value, or `None` if a write happened during the read.
+    #[inline]
+    pub fn try_read(&self) -> Option<T> {
+        let seq = self.seq.load(Ordering::Acquire);
+        if seq & 1 == 1 {
+            return None;
+        }
+
+        // The copy may be torn, it is only assumed initialized after the validation.
+        let value = unsafe { ptr::read_volatile(self.value.get() as *const MaybeUninit<T>) };
+        fence(Ordering::Acquire);
+        if self.seq.load(Ordering::Relaxed) == seq {
+            return Some(unsafe { value.assume_init() });
+        }
+        None
+    }
+
+    /// Modifies the value exclusively, concurrent writers are serialized.
+    pub fn write<F: FnOnce(&mut T)>(&self, f: F) {
+        let _guard = self.writer.write();
+        let seq = self.seq.load(Ordering::Relaxed);
+
+        // Guarantees the sequence is odd during the whole modification,
+        // even if `f` panics.
+        let _end = SeqEnd { seq: &self.seq, end: seq.wrapping_add(2) };
+        self.seq.store(seq.wrapping_add(1), Ordering::Relaxed);
+        fence(Ordering::Release);
+
+        f(unsafe { &mut *self.value.get() });
+    }
+
+    /// Replaces the value.
+    #[inline]
+    pub fn store(&self, value: T) {
+        self.write(|v| *v = value);
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<T: Copy + Default> Default for SeqLock<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+/// Makes the sequence even again when the write completes.
+struct SeqEnd<'a> {
+    seq: &'a AtomicU64,
+    end: u64,
+}
+
+impl Drop for SeqEnd<'_> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        self.seq.store(self.end, Ordering::Release);
+    }
+}
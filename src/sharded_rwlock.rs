@@ -0,0 +1,97 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use omango_util::cache_padded::CachePadded;
+
+use crate::rwlock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// An array of `N` reader-writer locks, each one protecting its own part of the data.
+///
+/// Threads accessing different shards do not contend with each other.
+/// Every shard is aligned to the CPU cache line to prevent false-sharing between them.
+pub struct ShardedRwLock<T, const N: usize = 64> {
+    shards: [CachePadded<RwLock<T>>; N],
+}
+
+impl<T, const N: usize> ShardedRwLock<T, N> {
+    /// Creates the shards with the values returned by `f` for each shard index.
+    #[inline]
+    pub fn from_fn<F: FnMut(usize) -> T>(mut f: F) -> Self {
+        assert!(N > 0, "ShardedRwLock must have at least one shard");
+        Self {
+            shards: std::array::from_fn(|idx| CachePadded::new(RwLock::new(f(idx)))),
+        }
+    }
+
+    /// Returns the shard for the index `idx % N`.
+    #[inline(always)]
+    pub fn shard(&self, idx: usize) -> &RwLock<T> {
+        &self.shards[idx % N]
+    }
+
+    /// Returns the shard which the key is hashed to.
+    #[inline]
+    pub fn shard_for<K: Hash + ?Sized>(&self, key: &K) -> &RwLock<T> {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        self.shard(hasher.finish() as usize)
+    }
+
+    /// Acquires the shared access of the shard for the index `idx % N`.
+    #[inline(always)]
+    pub fn read(&self, idx: usize) -> RwLockReadGuard<'_, T> {
+        self.shard(idx).read()
+    }
+
+    /// Acquires the exclusive access of the shard for the index `idx % N`.
+    #[inline(always)]
+    pub fn write(&self, idx: usize) -> RwLockWriteGuard<'_, T> {
+        self.shard(idx).write()
+    }
+
+    /// Acquires the exclusive access of all shards, in the index order.
+    pub fn write_all(&self) -> Vec<RwLockWriteGuard<'_, T>> {
+        self.shards.iter().map(|shard| shard.write()).collect()
+    }
+
+    #[inline(always)]
+    pub const fn shard_count(&self) -> usize {
+        N
+    }
+}
+
+impl<T: Default, const N: usize> ShardedRwLock<T, N> {
+    #[inline]
+    pub fn new() -> Self {
+        Self::from_fn(|_| T::default())
+    }
+}
+
+impl<T: Default, const N: usize> Default for ShardedRwLock<T, N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
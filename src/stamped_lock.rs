@@ -0,0 +1,125 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::sync::atomic::{AtomicU64, Ordering, fence};
+
+use crate::rwlock::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+/// A lock with three access modes: optimistic reads, shared reads and exclusive writes.
+///
+/// Every write bumps the stamp twice, it is odd while a write is in progress
+/// and even otherwise. An optimistic read only loads the stamp and validates it
+/// afterward, so it never blocks nor writes to shared memory. When the validation
+/// fails, the reader falls back to [`StampedLock::read_lock`].
+///
+/// The data read optimistically may be torn, it must only be used after the
+/// validation succeeded, and should be accessed through atomics.
+pub struct StampedLock {
+    stamp: AtomicU64,
+    lock: RwLock<()>,
+}
+
+impl StampedLock {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            stamp: AtomicU64::new(0),
+            lock: RwLock::new(()),
+        }
+    }
+
+    /// Returns a stamp for an optimistic read, without blocking.
+    ///
+    /// The stamp is odd, hence never valid, if a write is in progress.
+    #[inline(always)]
+    pub fn optimistic_read(&self) -> u64 {
+        self.stamp.load(Ordering::Acquire)
+    }
+
+    /// Returns `true` if no write has started since `stamp` was taken.
+    #[inline]
+    pub fn validate(&self, stamp: u64) -> bool {
+        // The reads of the data must complete before the stamp is loaded again.
+        fence(Ordering::Acquire);
+        stamp & 1 == 0 && self.stamp.load(Ordering::Relaxed) == stamp
+    }
+
+    /// Acquires the shared access, blocks while a writer holds the lock.
+    #[inline]
+    pub fn read_lock(&self) -> (u64, ReadGuard<'_>) {
+        let guard = self.lock.read();
+        (self.stamp.load(Ordering::Acquire), ReadGuard { _guard: guard })
+    }
+
+    /// Acquires the exclusive access, blocks until all readers and writers have left.
+    ///
+    /// Returns the odd stamp of the write in progress.
+    #[inline]
+    pub fn write_lock(&self) -> (u64, WriteGuard<'_>) {
+        let guard = self.lock.write();
+        let stamp = self.stamp.fetch_add(1, Ordering::Relaxed) + 1;
+        // The stamp must be odd before any write of the data is visible.
+        fence(Ordering::Release);
+        (stamp, WriteGuard { parent: self, _guard: guard })
+    }
+
+    /// Runs `f` under an optimistic read, falls back to a shared read if it was invalidated.
+    pub fn read<R, F: Fn() -> R>(&self, f: F) -> R {
+        let stamp = self.optimistic_read();
+        let result = f();
+        if self.validate(stamp) {
+            return result;
+        }
+        let _guard = self.read_lock();
+        f()
+    }
+
+    #[inline(always)]
+    pub fn is_write_locked(&self) -> bool {
+        self.stamp.load(Ordering::Relaxed) & 1 == 1
+    }
+}
+
+impl Default for StampedLock {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Releases the shared access of the [`StampedLock`] on drop.
+pub struct ReadGuard<'a> {
+    _guard: RwLockReadGuard<'a, ()>,
+}
+
+/// Releases the exclusive access of the [`StampedLock`] on drop,
+/// the stamp becomes even again.
+pub struct WriteGuard<'a> {
+    parent: &'a StampedLock,
+    _guard: RwLockWriteGuard<'a, ()>,
+}
+
+impl Drop for WriteGuard<'_> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        // The inner write guard is released after the stamp is even again.
+        self.parent.stamp.fetch_add(1, Ordering::Release);
+    }
+}
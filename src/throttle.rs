@@ -0,0 +1,91 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::time::{Duration, Instant};
+
+use omango_util::lock::RwSpinlock;
+
+/// Decides what happens to a call which comes before the interval has elapsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleMode {
+    /// Drops the call.
+    Skip,
+    /// Sleeps until the interval passes, then runs the call.
+    Block,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThrottleResult {
+    Executed,
+    Skipped,
+}
+
+/// Limits how often calls are executed, at most one call per `min_interval`.
+pub struct Throttle {
+    min_interval: Duration,
+    last: RwSpinlock<Option<Instant>>,
+}
+
+impl Throttle {
+    #[inline(always)]
+    pub fn new(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            last: RwSpinlock::new(None),
+        }
+    }
+
+    /// Runs `f` if the interval since the last executed call has elapsed,
+    /// otherwise skips it or blocks depending on `mode`.
+    pub fn call<F: FnOnce()>(&self, f: F, mode: ThrottleMode) -> ThrottleResult {
+        loop {
+            let remaining = self.try_reserve();
+            if remaining.is_zero() {
+                // The slot is reserved, so `f` runs without holding the lock.
+                f();
+                return ThrottleResult::Executed;
+            }
+            match mode {
+                ThrottleMode::Skip => return ThrottleResult::Skipped,
+                ThrottleMode::Block => std::thread::sleep(remaining),
+            }
+        }
+    }
+
+    #[inline(always)]
+    pub fn min_interval(&self) -> Duration {
+        self.min_interval
+    }
+
+    /// Records the current time as the last execution if the interval has elapsed,
+    /// otherwise returns the time left to wait.
+    fn try_reserve(&self) -> Duration {
+        let now = Instant::now();
+        let mut last = self.last.write();
+        if let Some(prev) = *last {
+            let elapsed = now.saturating_duration_since(prev);
+            if elapsed < self.min_interval {
+                return self.min_interval - elapsed;
+            }
+        }
+        *last = Some(now);
+        Duration::ZERO
+    }
+}
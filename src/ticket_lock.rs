@@ -0,0 +1,134 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::UnsafeCell,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::backoff::Backoff;
+
+/// A spinlock which grants the lock in the order the threads asked for it.
+///
+/// Each thread takes a ticket and spins until its number is served,
+/// so no waiter can be overtaken by a later one.
+pub struct TicketLock<T> {
+    next_ticket: AtomicUsize,
+    now_serving: AtomicUsize,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: Send> Send for TicketLock<T> {}
+
+unsafe impl<T: Send> Sync for TicketLock<T> {}
+
+impl<T> TicketLock<T> {
+    #[inline(always)]
+    pub const fn new(value: T) -> Self {
+        Self {
+            next_ticket: AtomicUsize::new(0),
+            now_serving: AtomicUsize::new(0),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Acquires the lock, spins until the ticket of the current thread is served.
+    pub fn lock(&self) -> TicketGuard<'_, T> {
+        let ticket = self.next_ticket.fetch_add(1, Ordering::Relaxed);
+        let mut backoff = Backoff::new();
+        while self.now_serving.load(Ordering::Acquire) != ticket {
+            backoff.snooze();
+        }
+        TicketGuard { parent: self, _marker: PhantomData }
+    }
+
+    /// Acquires the lock only if nobody holds or waits for it.
+    #[inline]
+    pub fn try_lock(&self) -> Option<TicketGuard<'_, T>> {
+        let serving = self.now_serving.load(Ordering::Acquire);
+        if self.next_ticket.compare_exchange(
+            serving,
+            serving.wrapping_add(1),
+            Ordering::Acquire,
+            Ordering::Relaxed,
+        ).is_ok() {
+            return Some(TicketGuard { parent: self, _marker: PhantomData });
+        }
+        None
+    }
+
+    #[inline(always)]
+    pub fn is_locked(&self) -> bool {
+        self.next_ticket.load(Ordering::Relaxed) != self.now_serving.load(Ordering::Relaxed)
+    }
+
+    #[inline(always)]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    #[inline(always)]
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<T: Default> Default for TicketLock<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+/// Serves the next ticket of the [`TicketLock`] on drop.
+pub struct TicketGuard<'a, T> {
+    parent: &'a TicketLock<T>,
+    // The guard must be dropped by the thread which locked the lock.
+    _marker: PhantomData<*const ()>,
+}
+
+unsafe impl<T: Sync> Sync for TicketGuard<'_, T> {}
+
+impl<T> Drop for TicketGuard<'_, T> {
+    #[inline(always)]
+    fn drop(&mut self) {
+        // Only the owner changes the served number.
+        let serving = self.parent.now_serving.load(Ordering::Relaxed);
+        self.parent.now_serving.store(serving.wrapping_add(1), Ordering::Release);
+    }
+}
+
+impl<T> Deref for TicketGuard<'_, T> {
+    type Target = T;
+
+    #[inline(always)]
+    fn deref(&self) -> &T {
+        unsafe { &*self.parent.value.get() }
+    }
+}
+
+impl<T> DerefMut for TicketGuard<'_, T> {
+    #[inline(always)]
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.parent.value.get() }
+    }
+}
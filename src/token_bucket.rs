@@ -0,0 +1,103 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use omango_util::lock::RwSpinlock;
+
+/// A rate limiter which hands out tokens refilled at a constant rate.
+///
+/// The bucket starts full. Tokens are added lazily on every call,
+/// based on the time elapsed since the last refill, and never exceed the capacity.
+pub struct TokenBucket {
+    capacity: f64,
+    tokens_per_ms: f64,
+    // (current tokens, last refill)
+    state: RwSpinlock<(f64, Instant)>,
+}
+
+impl TokenBucket {
+    #[inline]
+    pub fn new(capacity: u32, tokens_per_ms: f64) -> Self {
+        assert!(tokens_per_ms > 0.0, "the refill rate of TokenBucket must be positive");
+        Self {
+            capacity: capacity as f64,
+            tokens_per_ms,
+            state: RwSpinlock::new((capacity as f64, Instant::now())),
+        }
+    }
+
+    /// Takes `n` tokens, blocks the current thread until they are available.
+    ///
+    /// Panics if `n` is greater than the capacity, the call could never succeed.
+    pub fn acquire(&self, n: u32) {
+        assert!(n as f64 <= self.capacity, "acquire more tokens than the capacity of TokenBucket");
+        loop {
+            let missing = match self.take(n) {
+                Ok(_) => return,
+                Err(missing) => missing,
+            };
+
+            // Sleeps until the missing tokens are expected to be refilled.
+            let wait = Duration::from_secs_f64(missing / self.tokens_per_ms / 1000.0);
+            thread::sleep(wait.max(Duration::from_micros(1)));
+        }
+    }
+
+    /// Takes `n` tokens without blocking.
+    ///
+    /// Returns `false` if there are not enough tokens.
+    #[inline]
+    pub fn try_acquire(&self, n: u32) -> bool {
+        self.take(n).is_ok()
+    }
+
+    /// Returns the number of tokens currently available.
+    #[inline]
+    pub fn available(&self) -> u32 {
+        let mut state = self.state.write();
+        self.refill(&mut state);
+        state.0 as u32
+    }
+
+    /// Returns the number of missing tokens if the request can not be served.
+    fn take(&self, n: u32) -> Result<(), f64> {
+        let mut state = self.state.write();
+        self.refill(&mut state);
+
+        let n = n as f64;
+        if state.0 >= n {
+            state.0 -= n;
+            return Ok(());
+        }
+        Err(n - state.0)
+    }
+
+    #[inline]
+    fn refill(&self, state: &mut (f64, Instant)) {
+        let now = Instant::now();
+        let elapsed_ms = now.duration_since(state.1).as_secs_f64() * 1000.0;
+        state.0 = (state.0 + elapsed_ms * self.tokens_per_ms).min(self.capacity);
+        state.1 = now;
+    }
+}
@@ -0,0 +1,245 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::ops::{Deref, DerefMut};
+
+use omango_util::lock::{RwSpinlock, RwSpinlockGuard};
+
+use crate::{
+    fair_mutex::{FairMutex, FairMutexGuard},
+    mutex::{Mutex, MutexGuard},
+    rwlock::{RwLock, RwLockReadGuard, RwLockWriteGuard},
+    ticket_lock::{TicketGuard, TicketLock},
+};
+
+/// A lock which can be acquired without blocking.
+///
+/// The guard is a generic associated type, so the locking traits are not
+/// object-safe: they are meant for generic bounds, not for `dyn` objects.
+pub trait TryLock {
+    /// The type of the protected data.
+    type Data;
+
+    /// The guard which gives the exclusive access and releases the lock on drop.
+    type Guard<'a>: DerefMut<Target = Self::Data> where Self: 'a;
+
+    /// Acquires the lock without blocking, returns `None` if it is held.
+    fn try_lock(&self) -> Option<Self::Guard<'_>>;
+}
+
+/// A lock which can be acquired by blocking or spinning until it is available.
+pub trait Lock: TryLock {
+    /// Acquires the lock, waits until it is able to do so.
+    fn lock(&self) -> Self::Guard<'_>;
+}
+
+/// A lock which can be shared by many readers and acquired without blocking.
+///
+/// The read side of a reader-writer lock only gives `&Data`,
+/// so it has its own traits rather than implementing [`TryLock`].
+pub trait TryLockShared {
+    /// The type of the protected data.
+    type Data;
+
+    /// The guard which gives the shared access and releases it on drop.
+    type SharedGuard<'a>: Deref<Target = Self::Data> where Self: 'a;
+
+    /// Acquires the shared access without blocking, returns `None` if a writer holds it.
+    fn try_lock_shared(&self) -> Option<Self::SharedGuard<'_>>;
+}
+
+pub trait LockShared: TryLockShared {
+    /// Acquires the shared access, waits until it is able to do so.
+    fn lock_shared(&self) -> Self::SharedGuard<'_>;
+}
+
+impl<T> TryLock for Mutex<T> {
+    type Data = T;
+    type Guard<'a> = MutexGuard<'a, T> where Self: 'a;
+
+    #[inline(always)]
+    fn try_lock(&self) -> Option<Self::Guard<'_>> {
+        Mutex::try_lock(self)
+    }
+}
+
+impl<T> Lock for Mutex<T> {
+    #[inline(always)]
+    fn lock(&self) -> Self::Guard<'_> {
+        Mutex::lock(self)
+    }
+}
+
+impl<T> TryLock for FairMutex<T> {
+    type Data = T;
+    type Guard<'a> = FairMutexGuard<'a, T> where Self: 'a;
+
+    #[inline(always)]
+    fn try_lock(&self) -> Option<Self::Guard<'_>> {
+        FairMutex::try_lock(self)
+    }
+}
+
+impl<T> Lock for FairMutex<T> {
+    #[inline(always)]
+    fn lock(&self) -> Self::Guard<'_> {
+        FairMutex::lock(self)
+    }
+}
+
+impl<T> TryLock for TicketLock<T> {
+    type Data = T;
+    type Guard<'a> = TicketGuard<'a, T> where Self: 'a;
+
+    #[inline(always)]
+    fn try_lock(&self) -> Option<Self::Guard<'_>> {
+        TicketLock::try_lock(self)
+    }
+}
+
+impl<T> Lock for TicketLock<T> {
+    #[inline(always)]
+    fn lock(&self) -> Self::Guard<'_> {
+        TicketLock::lock(self)
+    }
+}
+
+/// The spinlock is locked exclusively, through its write side.
+impl<T> TryLock for RwSpinlock<T> {
+    type Data = T;
+    type Guard<'a> = RwSpinlockGuard<'a, T> where Self: 'a;
+
+    #[inline(always)]
+    fn try_lock(&self) -> Option<Self::Guard<'_>> {
+        self.try_write()
+    }
+}
+
+impl<T> Lock for RwSpinlock<T> {
+    #[inline(always)]
+    fn lock(&self) -> Self::Guard<'_> {
+        self.write()
+    }
+}
+
+impl<T> TryLockShared for RwSpinlock<T> {
+    type Data = T;
+    type SharedGuard<'a> = RwSpinlockGuard<'a, T> where Self: 'a;
+
+    #[inline(always)]
+    fn try_lock_shared(&self) -> Option<Self::SharedGuard<'_>> {
+        self.try_read()
+    }
+}
+
+impl<T> LockShared for RwSpinlock<T> {
+    #[inline(always)]
+    fn lock_shared(&self) -> Self::SharedGuard<'_> {
+        self.read()
+    }
+}
+
+impl<T> TryLock for RwLock<T> {
+    type Data = T;
+    type Guard<'a> = RwLockWriteGuard<'a, T> where Self: 'a;
+
+    #[inline(always)]
+    fn try_lock(&self) -> Option<Self::Guard<'_>> {
+        self.try_write()
+    }
+}
+
+impl<T> Lock for RwLock<T> {
+    #[inline(always)]
+    fn lock(&self) -> Self::Guard<'_> {
+        self.write()
+    }
+}
+
+impl<T> TryLockShared for RwLock<T> {
+    type Data = T;
+    type SharedGuard<'a> = RwLockReadGuard<'a, T> where Self: 'a;
+
+    #[inline(always)]
+    fn try_lock_shared(&self) -> Option<Self::SharedGuard<'_>> {
+        self.try_read()
+    }
+}
+
+impl<T> LockShared for RwLock<T> {
+    #[inline(always)]
+    fn lock_shared(&self) -> Self::SharedGuard<'_> {
+        self.read()
+    }
+}
+
+/// Locks the shared side of a [`RwLock`].
+pub struct ReadLock<'a, T>(&'a RwLock<T>);
+
+impl<'a, T> ReadLock<'a, T> {
+    #[inline(always)]
+    pub fn new(lock: &'a RwLock<T>) -> Self {
+        Self(lock)
+    }
+}
+
+impl<T> TryLockShared for ReadLock<'_, T> {
+    type Data = T;
+    type SharedGuard<'b> = RwLockReadGuard<'b, T> where Self: 'b;
+
+    #[inline(always)]
+    fn try_lock_shared(&self) -> Option<Self::SharedGuard<'_>> {
+        self.0.try_read()
+    }
+}
+
+impl<T> LockShared for ReadLock<'_, T> {
+    #[inline(always)]
+    fn lock_shared(&self) -> Self::SharedGuard<'_> {
+        self.0.read()
+    }
+}
+
+/// Locks the exclusive side of a [`RwLock`].
+pub struct WriteLock<'a, T>(&'a RwLock<T>);
+
+impl<'a, T> WriteLock<'a, T> {
+    #[inline(always)]
+    pub fn new(lock: &'a RwLock<T>) -> Self {
+        Self(lock)
+    }
+}
+
+impl<T> TryLock for WriteLock<'_, T> {
+    type Data = T;
+    type Guard<'b> = RwLockWriteGuard<'b, T> where Self: 'b;
+
+    #[inline(always)]
+    fn try_lock(&self) -> Option<Self::Guard<'_>> {
+        self.0.try_write()
+    }
+}
+
+impl<T> Lock for WriteLock<'_, T> {
+    #[inline(always)]
+    fn lock(&self) -> Self::Guard<'_> {
+        self.0.write()
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use omango_util::lock::RwSpinlock;
+
+/// A cell which pairs its value with a version number for optimistic concurrency.
+///
+/// Readers take a snapshot with its version, writers only succeed
+/// if the version has not changed since their snapshot.
+pub struct VersionedCell<T: Clone> {
+    inner: RwSpinlock<(T, u64)>,
+}
+
+impl<T: Clone> VersionedCell<T> {
+    #[inline(always)]
+    pub fn new(value: T) -> Self {
+        Self {
+            inner: RwSpinlock::new((value, 0)),
+        }
+    }
+
+    /// Returns a snapshot of the value with its version.
+    #[inline]
+    pub fn read(&self) -> (T, u64) {
+        let guard = self.inner.read();
+        (guard.0.clone(), guard.1)
+    }
+
+    /// Replaces the value if the version is still `expected_version`,
+    /// the version is incremented on success.
+    pub fn compare_and_update(&self, expected_version: u64, new_value: T) -> bool {
+        let mut guard = self.inner.write();
+        if guard.1 != expected_version {
+            return false;
+        }
+        guard.0 = new_value;
+        guard.1 += 1;
+        true
+    }
+
+    /// Applies `f` to the latest snapshot and retries until the update succeeds.
+    ///
+    /// `f` may be called several times under contention.
+    pub fn update<F: FnMut(&T) -> T>(&self, mut f: F) {
+        loop {
+            let (value, version) = self.read();
+            if self.compare_and_update(version, f(&value)) {
+                return;
+            }
+        }
+    }
+
+    #[inline]
+    pub fn version(&self) -> u64 {
+        self.inner.read().1
+    }
+}
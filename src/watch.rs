@@ -0,0 +1,134 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::Cell,
+    sync::{
+        Arc,
+        atomic::{AtomicU32, AtomicU64, Ordering},
+    },
+};
+
+use omango_util::lock::RwSpinlock;
+
+/// Creates a channel which broadcasts the latest sent value to all receivers.
+///
+/// Only the latest value is kept, receivers which are slower than the sender
+/// skip the older values. There is no backpressure.
+#[inline]
+pub fn channel<T: Clone + Send + Sync>() -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Shared {
+        version: AtomicU64::new(0),
+        value: RwSpinlock::new(None),
+        futex: AtomicU32::new(0),
+    });
+    let receiver = Receiver {
+        shared: shared.clone(),
+        seen: Cell::new(0),
+    };
+    (Sender { shared }, receiver)
+}
+
+struct Shared<T> {
+    version: AtomicU64,
+    value: RwSpinlock<Option<T>>,
+    futex: AtomicU32,
+}
+
+/// The sending side of the watch channel.
+pub struct Sender<T> {
+    shared: Arc<Shared<T>>,
+}
+
+impl<T: Clone + Send + Sync> Sender<T> {
+    /// Replaces the latest value and wakes up all blocked receivers.
+    pub fn send(&self, value: T) {
+        {
+            // The version is bumped under the lock,
+            // so readers always see a value with its matching version.
+            let mut guard = self.shared.value.write();
+            *guard = Some(value);
+            self.shared.version.fetch_add(1, Ordering::Release);
+        }
+        self.shared.futex.fetch_add(1, Ordering::Release);
+        omango_futex::wake_all(&self.shared.futex);
+    }
+
+    /// Creates a new receiver which has not observed any value yet.
+    #[inline]
+    pub fn subscribe(&self) -> Receiver<T> {
+        Receiver {
+            shared: self.shared.clone(),
+            seen: Cell::new(0),
+        }
+    }
+}
+
+/// The receiving side of the watch channel.
+///
+/// Every receiver tracks the version it observed last,
+/// so cloned receivers see the same values independently.
+pub struct Receiver<T> {
+    shared: Arc<Shared<T>>,
+    seen: Cell<u64>,
+}
+
+impl<T: Clone + Send + Sync> Receiver<T> {
+    /// Blocks the current thread until a value newer than the last observed one is sent.
+    pub fn recv(&self) -> T {
+        loop {
+            // The counter is read before checking the version,
+            // so a value sent in between is not missed.
+            let seq = self.shared.futex.load(Ordering::Acquire);
+            if let Some(value) = self.try_recv() {
+                return value;
+            }
+            omango_futex::wait(&self.shared.futex, seq);
+        }
+    }
+
+    /// Returns the latest value if it has not been observed yet, without blocking.
+    pub fn try_recv(&self) -> Option<T> {
+        if self.shared.version.load(Ordering::Acquire) == self.seen.get() {
+            return None;
+        }
+
+        let guard = self.shared.value.read();
+        let version = self.shared.version.load(Ordering::Acquire);
+        self.seen.set(version);
+        guard.clone()
+    }
+
+    /// Returns `true` if a value newer than the last observed one is available.
+    #[inline(always)]
+    pub fn has_changed(&self) -> bool {
+        self.shared.version.load(Ordering::Acquire) != self.seen.get()
+    }
+}
+
+impl<T> Clone for Receiver<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            shared: self.shared.clone(),
+            seen: Cell::new(self.seen.get()),
+        }
+    }
+}
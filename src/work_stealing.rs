@@ -0,0 +1,300 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::{Cell, UnsafeCell},
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ptr,
+    sync::atomic::Ordering,
+};
+
+use crate::{
+    hazard::HazardDomain,
+    shim::{Arc, AtomicPtr, AtomicUsize, fence},
+};
+
+const MIN_CAPACITY: usize = 32;
+
+/// The result of a steal attempt.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Steal<T> {
+    /// An item was taken from the top of the deque.
+    Success(T),
+    /// The deque was empty.
+    Empty,
+    /// The steal lost a race with another thread and should be retried.
+    Retry,
+}
+
+struct Buffer<T> {
+    mask: usize,
+    slots: Box<[UnsafeCell<MaybeUninit<T>>]>,
+}
+
+impl<T> Buffer<T> {
+    fn alloc(capacity: usize) -> *mut Self {
+        debug_assert!(capacity.is_power_of_two());
+        let slots = (0..capacity)
+            .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+            .collect();
+        Box::into_raw(Box::new(Self {
+            mask: capacity - 1,
+            slots,
+        }))
+    }
+
+    #[inline(always)]
+    fn capacity(&self) -> usize {
+        self.mask + 1
+    }
+
+    #[inline(always)]
+    unsafe fn write(&self, index: usize, value: T) {
+        unsafe { (*self.slots[index & self.mask].get()).write(value) };
+    }
+
+    #[inline(always)]
+    unsafe fn read(&self, index: usize) -> T {
+        unsafe { (*self.slots[index & self.mask].get()).assume_init_read() }
+    }
+
+    // Copies the slot without asserting it holds a valid item, it may be
+    // overwritten or taken concurrently until the caller wins the top.
+    #[inline(always)]
+    unsafe fn read_uninit(&self, index: usize) -> MaybeUninit<T> {
+        unsafe { ptr::read(self.slots[index & self.mask].get()) }
+    }
+}
+
+// Frees the memory of a buffer, the items were moved out or copied to a new buffer.
+unsafe fn free_buffer<T>(buffer: *mut Buffer<T>) {
+    drop(unsafe { Box::from_raw(buffer) });
+}
+
+struct Inner<T> {
+    top: AtomicUsize,
+    bottom: AtomicUsize,
+    buffer: AtomicPtr<Buffer<T>>,
+    // Protects the buffer read by the stealers from being freed by a concurrent growth.
+    hazard: HazardDomain,
+}
+
+unsafe impl<T: Send> Send for Inner<T> {}
+
+unsafe impl<T: Send> Sync for Inner<T> {}
+
+impl<T> Drop for Inner<T> {
+    fn drop(&mut self) {
+        // Loom atomics have no `get_mut`, the loads are uncontended anyway.
+        let buffer = self.buffer.load(Ordering::Relaxed);
+        let bottom = self.bottom.load(Ordering::Relaxed);
+        let mut top = self.top.load(Ordering::Relaxed);
+        unsafe {
+            while top != bottom {
+                drop((*buffer).read(top));
+                top = top.wrapping_add(1);
+            }
+            free_buffer(buffer);
+        }
+    }
+}
+
+/// A Chase-Lev work-stealing deque.
+///
+/// The owner pushes and pops items at the bottom, like a stack, while
+/// the [`Stealer`]s take items from the top, like a queue. The circular buffer
+/// grows when it is full, the old buffers are reclaimed with hazard pointers.
+pub struct WorkStealingDeque<T> {
+    inner: Arc<Inner<T>>,
+    // Only the owner thread pushes and pops, so the deque can not be shared.
+    _marker: PhantomData<Cell<()>>,
+}
+
+impl<T: Send + 'static> WorkStealingDeque<T> {
+    #[inline]
+    pub fn new() -> Self {
+        Self::with_capacity(MIN_CAPACITY)
+    }
+
+    /// Creates a deque whose buffer holds at least `capacity` items before growing.
+    pub fn with_capacity(capacity: usize) -> Self {
+        let capacity = capacity.max(MIN_CAPACITY).next_power_of_two();
+        Self {
+            inner: Arc::new(Inner {
+                top: AtomicUsize::new(0),
+                bottom: AtomicUsize::new(0),
+                buffer: AtomicPtr::new(Buffer::alloc(capacity)),
+                hazard: HazardDomain::new(),
+            }),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a handle which steals items from the top of the deque.
+    #[inline]
+    pub fn stealer(&self) -> Stealer<T> {
+        Stealer {
+            inner: self.inner.clone(),
+        }
+    }
+
+    /// Pushes an item to the bottom of the deque.
+    pub fn push(&self, value: T) {
+        let inner = &*self.inner;
+        let bottom = inner.bottom.load(Ordering::Relaxed);
+        let top = inner.top.load(Ordering::Acquire);
+        let mut buffer = inner.buffer.load(Ordering::Relaxed);
+
+        if bottom.wrapping_sub(top) >= unsafe { (*buffer).capacity() } {
+            buffer = self.grow(buffer, top, bottom);
+        }
+        unsafe { (*buffer).write(bottom, value) };
+
+        // The item must be visible before the stealers see the new bottom.
+        fence(Ordering::Release);
+        inner.bottom.store(bottom.wrapping_add(1), Ordering::Relaxed);
+    }
+
+    /// Pops the most recently pushed item from the bottom of the deque.
+    pub fn pop(&self) -> Option<T> {
+        let inner = &*self.inner;
+        let bottom = inner.bottom.load(Ordering::Relaxed).wrapping_sub(1);
+        let buffer = inner.buffer.load(Ordering::Relaxed);
+        inner.bottom.store(bottom, Ordering::Relaxed);
+
+        // The new bottom must be visible to the stealers before reading the top,
+        // so the last item is either popped or stolen, never both.
+        fence(Ordering::SeqCst);
+        let top = inner.top.load(Ordering::Relaxed);
+
+        let len = bottom.wrapping_sub(top) as isize;
+        if len < 0 {
+            inner.bottom.store(bottom.wrapping_add(1), Ordering::Relaxed);
+            return None;
+        }
+
+        if len > 0 {
+            return Some(unsafe { (*buffer).read(bottom) });
+        }
+
+        // The last item, the stealers compete for it on the top.
+        let value = unsafe { (*buffer).read_uninit(bottom) };
+        let won = inner.top.compare_exchange(
+            top,
+            top.wrapping_add(1),
+            Ordering::SeqCst,
+            Ordering::Relaxed,
+        ).is_ok();
+        inner.bottom.store(bottom.wrapping_add(1), Ordering::Relaxed);
+        if !won {
+            return None;
+        }
+        Some(unsafe { value.assume_init() })
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        let bottom = self.inner.bottom.load(Ordering::Relaxed);
+        let top = self.inner.top.load(Ordering::Relaxed);
+        (bottom.wrapping_sub(top) as isize).max(0) as usize
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Moves the items to a buffer twice as large and retires the old one.
+    #[cold]
+    fn grow(&self, old: *mut Buffer<T>, top: usize, bottom: usize) -> *mut Buffer<T> {
+        let inner = &*self.inner;
+        let new = Buffer::alloc(unsafe { (*old).capacity() } * 2);
+        let mut index = top;
+        while index != bottom {
+            unsafe { (*new).write(index, (*old).read(index)) };
+            index = index.wrapping_add(1);
+        }
+        inner.buffer.store(new, Ordering::Release);
+
+        // The stealers may still be reading the old buffer, it is freed once they are done.
+        unsafe { inner.hazard.retire(old, free_buffer::<T>) };
+        inner.hazard.flush();
+        new
+    }
+}
+
+impl<T: Send + 'static> Default for WorkStealingDeque<T> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Steals items from the top of a [`WorkStealingDeque`], it can be shared between threads.
+pub struct Stealer<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T: Send> Stealer<T> {
+    /// Takes the oldest item from the top of the deque.
+    pub fn steal(&self) -> Steal<T> {
+        let inner = &*self.inner;
+        let top = inner.top.load(Ordering::Acquire);
+
+        // Pairs with the fence of "pop", see there.
+        fence(Ordering::SeqCst);
+        let bottom = inner.bottom.load(Ordering::Acquire);
+        if bottom.wrapping_sub(top) as isize <= 0 {
+            return Steal::Empty;
+        }
+
+        let record = inner.hazard.protect_load(&inner.buffer);
+        let value = unsafe { (*record.as_ptr()).read_uninit(top) };
+        drop(record);
+
+        if inner.top.compare_exchange(
+            top,
+            top.wrapping_add(1),
+            Ordering::SeqCst,
+            Ordering::Relaxed,
+        ).is_err() {
+            // Another thread took the item, the copy may be torn and is never used.
+            return Steal::Retry;
+        }
+        Steal::Success(unsafe { value.assume_init() })
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        let top = self.inner.top.load(Ordering::Relaxed);
+        let bottom = self.inner.bottom.load(Ordering::Relaxed);
+        bottom.wrapping_sub(top) as isize <= 0
+    }
+}
+
+impl<T> Clone for Stealer<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
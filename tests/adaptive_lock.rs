@@ -0,0 +1,92 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{cell::Cell, sync::Arc, thread, time::Duration};
+
+use omango_sync::adaptive_lock::{AdaptiveLock, AdaptiveLockGuard};
+
+// Compiles only if the type does not implement the trait,
+// otherwise the call is ambiguous between the two impls.
+macro_rules! assert_not_impl {
+    ($ty:ty: $tr:path) => {{
+        trait AmbiguousIfImpl<A> {
+            fn some_item() {}
+        }
+        impl<T: ?Sized> AmbiguousIfImpl<()> for T {}
+        impl<T: ?Sized + $tr> AmbiguousIfImpl<u8> for T {}
+        <$ty as AmbiguousIfImpl<_>>::some_item()
+    }};
+}
+
+fn assert_sync<T: Sync>() {}
+
+#[test]
+fn test_guard_auto_traits() {
+    assert_sync::<AdaptiveLockGuard<'static, i32>>();
+    assert_not_impl!(AdaptiveLockGuard<'static, i32>: Send);
+    assert_not_impl!(AdaptiveLockGuard<'static, Cell<i32>>: Sync);
+}
+
+/// Runs one contended acquisition, which is when the spin limit is tuned.
+fn contend(lock: &Arc<AdaptiveLock<u32>>) {
+    let guard = lock.lock();
+    let lock_clone = lock.clone();
+    let handle = thread::spawn(move || *lock_clone.lock() += 1);
+    thread::sleep(Duration::from_millis(5));
+    drop(guard);
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_spin_limit_grows_under_short_holds() {
+    let lock = Arc::new(AdaptiveLock::new(0, 16));
+    for _ in 0..16 {
+        *lock.lock() += 1;
+    }
+    contend(&lock);
+    assert!(lock.spin_limit() > 16);
+}
+
+#[test]
+fn test_spin_limit_shrinks_under_long_holds() {
+    let lock = Arc::new(AdaptiveLock::new(0, 16));
+    for _ in 0..8 {
+        let _guard = lock.lock();
+        thread::sleep(Duration::from_millis(1));
+    }
+    // The contended acquisition itself records a long hold too.
+    contend(&lock);
+    assert!(lock.spin_limit() < 16);
+}
+
+#[test]
+fn test_contention() {
+    let lock = AdaptiveLock::new(0u64, 100);
+    thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| {
+                for _ in 0..5_000 {
+                    *lock.lock() += 1;
+                }
+            });
+        }
+    });
+    assert_eq!(lock.into_inner(), 40_000);
+}
@@ -0,0 +1,144 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    thread,
+};
+
+use omango_sync::arc_swap::ArcSwap;
+
+/// Counts the live instances, so a leaked or doubly freed value is noticed.
+struct Tracked<'a> {
+    value: usize,
+    live: &'a AtomicUsize,
+}
+
+impl<'a> Tracked<'a> {
+    fn new(value: usize, live: &'a AtomicUsize) -> Arc<Self> {
+        live.fetch_add(1, Ordering::SeqCst);
+        Arc::new(Self { value, live })
+    }
+}
+
+impl Drop for Tracked<'_> {
+    fn drop(&mut self) {
+        self.live.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn test_load_store() {
+    let swap = ArcSwap::from_pointee(1);
+    assert_eq!(**swap.load(), 1);
+
+    swap.store(Arc::new(2));
+    assert_eq!(*swap.load_full(), 2);
+}
+
+#[test]
+fn test_swap() {
+    let first = Arc::new(1);
+    let swap = ArcSwap::new(first.clone());
+    let old = swap.swap(Arc::new(2));
+    assert!(Arc::ptr_eq(&old, &first));
+    assert_eq!(**swap.load(), 2);
+}
+
+#[test]
+fn test_compare_and_swap() {
+    let first = Arc::new(1);
+    let swap = ArcSwap::new(first.clone());
+
+    let stale = Arc::new(1);
+    let rejected = swap.compare_and_swap(&stale, Arc::new(3)).unwrap_err();
+    assert_eq!(*rejected, 3);
+
+    let old = swap.compare_and_swap(&first, Arc::new(2)).unwrap();
+    assert!(Arc::ptr_eq(&old, &first));
+    assert!(swap.load().ptr_eq(&swap.load_full()));
+    assert_eq!(**swap.load(), 2);
+}
+
+#[test]
+fn test_guard_keeps_value_alive() {
+    let live = AtomicUsize::new(0);
+    let swap = ArcSwap::new(Tracked::new(1, &live));
+    let guard = swap.load();
+    swap.store(Tracked::new(2, &live));
+
+    // The replaced value is still referenced by the guard.
+    assert_eq!(live.load(Ordering::SeqCst), 2);
+    assert_eq!(guard.value, 1);
+    drop(guard);
+    assert_eq!(live.load(Ordering::SeqCst), 1);
+
+    drop(swap);
+    assert_eq!(live.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+fn test_no_leak_under_concurrent_loads_and_stores() {
+    const LOADERS: usize = 4;
+    const STORERS: usize = 2;
+    const STORES: usize = 10_000;
+
+    let live = AtomicUsize::new(0);
+    let swap = ArcSwap::new(Tracked::new(0, &live));
+    let stop = AtomicBool::new(false);
+    thread::scope(|s| {
+        for _ in 0..LOADERS {
+            s.spawn(|| {
+                while !stop.load(Ordering::SeqCst) {
+                    let guard = swap.load();
+                    assert!(guard.value <= STORERS * STORES);
+                }
+            });
+        }
+
+        let storers: Vec<_> = (0..STORERS)
+            .map(|storer| {
+                let (swap, live) = (&swap, &live);
+                s.spawn(move || {
+                    for i in 1..=STORES {
+                        let value = storer * STORES + i;
+                        if i % 2 == 0 {
+                            swap.store(Tracked::new(value, live));
+                        } else {
+                            let current = swap.load_full();
+                            drop(swap.compare_and_swap(&current, Tracked::new(value, live)));
+                        }
+                    }
+                })
+            })
+            .collect();
+        for storer in storers {
+            storer.join().unwrap();
+        }
+        stop.store(true, Ordering::SeqCst);
+    });
+
+    // Only the current value is alive, every replaced one was freed exactly once.
+    assert_eq!(live.load(Ordering::SeqCst), 1);
+    drop(swap);
+    assert_eq!(live.load(Ordering::SeqCst), 0);
+}
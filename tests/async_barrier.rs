@@ -0,0 +1,84 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+use std::{
+    cell::Cell,
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use omango_sync::async_barrier::AsyncBarrier;
+
+#[tokio::test]
+async fn test_single_party() {
+    let barrier = AsyncBarrier::new(1);
+    assert!(barrier.wait().await.is_leader());
+    assert!(barrier.wait().await.is_leader());
+}
+
+#[tokio::test]
+async fn test_one_leader() {
+    let barrier = AsyncBarrier::new(4);
+    let results = tokio::join!(barrier.wait(), barrier.wait(), barrier.wait(), barrier.wait());
+    let results = [results.0, results.1, results.2, results.3];
+    assert_eq!(results.iter().filter(|result| result.is_leader()).count(), 1);
+}
+
+#[tokio::test]
+async fn test_no_task_proceeds_early() {
+    let barrier = AsyncBarrier::new(3);
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut first = pin!(barrier.wait());
+    let mut second = pin!(barrier.wait());
+    assert!(first.as_mut().poll(&mut cx).is_pending());
+    assert!(second.as_mut().poll(&mut cx).is_pending());
+    // Polling again is a spurious wakeup, it does not count as an arrival.
+    assert!(first.as_mut().poll(&mut cx).is_pending());
+
+    assert!(barrier.wait().await.is_leader());
+    assert!(matches!(first.as_mut().poll(&mut cx), Poll::Ready(result) if !result.is_leader()));
+    assert!(matches!(second.as_mut().poll(&mut cx), Poll::Ready(result) if !result.is_leader()));
+}
+
+#[tokio::test]
+async fn test_cycles() {
+    const TASKS: usize = 3;
+    const CYCLES: usize = 50;
+
+    let barrier = AsyncBarrier::new(TASKS as u32);
+    let arrived = Cell::new(0);
+    let leaders = Cell::new(0);
+    let task = || {
+        let (barrier, arrived, leaders) = (&barrier, &arrived, &leaders);
+        async move {
+            for cycle in 0..CYCLES {
+                arrived.set(arrived.get() + 1);
+                if barrier.wait().await.is_leader() {
+                    leaders.set(leaders.get() + 1);
+                }
+                // Every task of the cycle has arrived before any proceeds.
+                assert!(arrived.get() >= (cycle + 1) * TASKS);
+                tokio::task::yield_now().await;
+            }
+        }
+    };
+    tokio::join!(task(), task(), task());
+    assert_eq!(leaders.get(), CYCLES);
+}
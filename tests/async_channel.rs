@@ -0,0 +1,134 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+use std::{
+    future::Future,
+    pin::pin,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+use omango_sync::async_channel::{async_bounded, AsyncReceiver};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn test_receiver_auto_traits() {
+    // Receiving takes `&mut self`, so a shared receiver can not receive.
+    assert_send_sync::<AsyncReceiver<i32>>();
+}
+
+#[tokio::test]
+async fn test_send_recv() {
+    let (sender, mut receiver) = async_bounded(2);
+    sender.send(1).await.unwrap();
+    sender.try_send(2).unwrap();
+    assert_eq!(sender.try_send(3), Err(3));
+
+    assert_eq!(receiver.recv().await, Some(1));
+    assert_eq!(receiver.try_recv(), Some(2));
+    assert_eq!(receiver.try_recv(), None);
+}
+
+#[tokio::test]
+async fn test_full_channel_suspends_sender() {
+    let (sender, mut receiver) = async_bounded(1);
+    sender.send(1).await.unwrap();
+
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut send = pin!(sender.send(2));
+    assert!(send.as_mut().poll(&mut cx).is_pending());
+
+    assert_eq!(receiver.recv().await, Some(1));
+    assert!(matches!(send.as_mut().poll(&mut cx), Poll::Ready(Ok(()))));
+    assert_eq!(receiver.recv().await, Some(2));
+}
+
+#[tokio::test]
+async fn test_drains_after_senders_dropped() {
+    let (sender, mut receiver) = async_bounded(4);
+    let other = sender.clone();
+    sender.send(1).await.unwrap();
+    other.send(2).await.unwrap();
+    drop(sender);
+    drop(other);
+
+    assert_eq!(receiver.recv().await, Some(1));
+    assert_eq!(receiver.recv().await, Some(2));
+    assert_eq!(receiver.recv().await, None);
+}
+
+#[tokio::test]
+async fn test_send_fails_after_receiver_dropped() {
+    let (sender, receiver) = async_bounded(1);
+    drop(receiver);
+    assert_eq!(sender.send(1).await, Err(1));
+}
+
+#[tokio::test]
+async fn test_backpressure_on_one_thread() {
+    const ITEMS: u64 = 1_000;
+
+    let (sender, mut receiver) = async_bounded(4);
+    let producer = |offset: u64| {
+        let sender = sender.clone();
+        async move {
+            for i in 0..ITEMS {
+                sender.send(offset + i).await.unwrap();
+            }
+        }
+    };
+    let consumer = async {
+        let mut received = Vec::new();
+        while received.len() < 2 * ITEMS as usize {
+            received.push(receiver.recv().await.unwrap());
+        }
+        received
+    };
+    let (_, _, mut received) = tokio::join!(producer(0), producer(ITEMS), consumer);
+
+    received.sort_unstable();
+    assert_eq!(received, (0..2 * ITEMS).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_drops_unreceived_items() {
+    let drops = Arc::new(AtomicUsize::new(0));
+
+    struct Tracked(Arc<AtomicUsize>);
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    let (sender, receiver) = async_bounded(4);
+    for _ in 0..3 {
+        assert!(sender.try_send(Tracked(drops.clone())).is_ok());
+    }
+    // The receiver can be moved to another thread.
+    thread::spawn(move || drop(receiver)).join().unwrap();
+    drop(sender);
+    assert_eq!(drops.load(Ordering::SeqCst), 3);
+}
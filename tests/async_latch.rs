@@ -0,0 +1,87 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+use std::{
+    cell::Cell,
+    thread,
+    time::{Duration, Instant},
+};
+
+use omango_sync::async_latch::AsyncCountdownLatch;
+
+#[tokio::test]
+async fn test_zero_is_open() {
+    let latch = AsyncCountdownLatch::new(0);
+    assert!(latch.is_open());
+    latch.await_zero().await;
+}
+
+#[tokio::test]
+async fn test_stays_open() {
+    let latch = AsyncCountdownLatch::new(2);
+    latch.count_down();
+    assert_eq!(latch.count(), 1);
+    assert!(!latch.is_open());
+
+    latch.count_down();
+    latch.count_down();
+    assert_eq!(latch.count(), 0);
+    assert!(latch.is_open());
+    latch.await_zero().await;
+    latch.await_zero().await;
+}
+
+#[tokio::test]
+async fn test_final_count_down_wakes_all() {
+    let latch = AsyncCountdownLatch::new(2);
+    let opened_at = Cell::new(None);
+    let waiter = || {
+        let (latch, opened_at) = (&latch, &opened_at);
+        async move {
+            latch.await_zero().await;
+            let opened_at: Instant = opened_at.get().unwrap();
+            assert!(opened_at.elapsed() < Duration::from_millis(1));
+        }
+    };
+    let counter = async {
+        latch.count_down();
+        tokio::task::yield_now().await;
+        assert!(!latch.is_open());
+
+        opened_at.set(Some(Instant::now()));
+        latch.count_down();
+    };
+    tokio::join!(waiter(), waiter(), waiter(), counter);
+}
+
+#[test]
+fn test_count_down_from_thread() {
+    let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+    let latch = AsyncCountdownLatch::new(3);
+    thread::scope(|s| {
+        s.spawn(|| {
+            for _ in 0..3 {
+                thread::sleep(Duration::from_millis(5));
+                latch.count_down();
+            }
+        });
+        runtime.block_on(latch.await_zero());
+    });
+    assert!(latch.is_open());
+}
@@ -0,0 +1,129 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+use std::{
+    cell::{Cell, RefCell},
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+use omango_sync::async_mutex::{AsyncMutex, AsyncMutexGuard};
+
+// Compiles only if the type does not implement the trait,
+// otherwise the call is ambiguous between the two impls.
+macro_rules! assert_not_impl {
+    ($ty:ty: $tr:path) => {{
+        trait AmbiguousIfImpl<A> {
+            fn some_item() {}
+        }
+        impl<T: ?Sized> AmbiguousIfImpl<()> for T {}
+        impl<T: ?Sized + $tr> AmbiguousIfImpl<u8> for T {}
+        <$ty as AmbiguousIfImpl<_>>::some_item()
+    }};
+}
+
+fn assert_send<T: Send>() {}
+
+fn assert_sync<T: Sync>() {}
+
+#[test]
+fn test_guard_auto_traits() {
+    assert_send::<AsyncMutexGuard<'static, i32>>();
+    assert_sync::<AsyncMutexGuard<'static, i32>>();
+    // Sharing the guard would share the data, which is only Send.
+    assert_send::<AsyncMutexGuard<'static, Cell<i32>>>();
+    assert_not_impl!(AsyncMutexGuard<'static, Cell<i32>>: Sync);
+}
+
+#[tokio::test]
+async fn test_try_lock() {
+    let mutex = AsyncMutex::new(0);
+    let mut guard = mutex.try_lock().unwrap();
+    *guard += 1;
+    assert!(mutex.is_locked());
+    assert!(mutex.try_lock().is_none());
+
+    drop(guard);
+    assert!(!mutex.is_locked());
+    assert_eq!(*mutex.lock().await, 1);
+    assert_eq!(mutex.into_inner(), 1);
+}
+
+#[tokio::test]
+async fn test_lock_suspends_task() {
+    let mutex = AsyncMutex::new(());
+    let guard = mutex.lock().await;
+
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut lock = pin!(mutex.lock());
+    assert!(lock.as_mut().poll(&mut cx).is_pending());
+
+    drop(guard);
+    assert!(matches!(lock.as_mut().poll(&mut cx), Poll::Ready(_)));
+}
+
+#[tokio::test]
+async fn test_guard_held_across_await() {
+    // Every task appends twice while it holds the lock, the pairs must not interleave.
+    let mutex = AsyncMutex::new(Vec::new());
+    let task = |id: usize| {
+        let mutex = &mutex;
+        async move {
+            for _ in 0..50 {
+                let mut guard = mutex.lock().await;
+                guard.push(id);
+                tokio::task::yield_now().await;
+                guard.push(id);
+            }
+        }
+    };
+    tokio::join!(task(0), task(1), task(2), task(3));
+
+    let log = mutex.into_inner();
+    assert_eq!(log.len(), 4 * 50 * 2);
+    for pair in log.chunks(2) {
+        assert_eq!(pair[0], pair[1]);
+    }
+}
+
+#[test]
+fn test_runtimes_on_many_threads() {
+    const THREADS: usize = 4;
+    const LOCKS: usize = 1_000;
+
+    let mutex = Arc::new(AsyncMutex::new(RefCell::new(0)));
+    thread::scope(|s| {
+        for _ in 0..THREADS {
+            let mutex = mutex.clone();
+            s.spawn(move || {
+                let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+                runtime.block_on(async {
+                    for _ in 0..LOCKS {
+                        let guard = mutex.lock().await;
+                        *guard.borrow_mut() += 1;
+                    }
+                });
+            });
+        }
+    });
+    assert_eq!(*mutex.try_lock().unwrap().borrow(), THREADS * LOCKS);
+}
@@ -0,0 +1,113 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+use std::{
+    cell::Cell,
+    future::Future,
+    pin::pin,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    task::{Context, Wake, Waker},
+};
+
+use omango_sync::async_notify::AsyncNotify;
+
+/// Records whether the task was woken.
+#[derive(Default)]
+struct Flag(AtomicBool);
+
+impl Wake for Flag {
+    fn wake(self: Arc<Self>) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test]
+async fn test_stored_permit() {
+    let notify = AsyncNotify::new();
+    assert!(!notify.try_notified());
+
+    notify.notify_one();
+    // The permit was stored, the wait completes immediately.
+    notify.notified().await;
+    assert!(!notify.try_notified());
+}
+
+#[tokio::test]
+async fn test_notify_all_wakes_all_waiters() {
+    let notify = AsyncNotify::new();
+    let woken = Cell::new(0);
+    let waiter = || {
+        let (notify, woken) = (&notify, &woken);
+        async move {
+            notify.notified().await;
+            woken.set(woken.get() + 1);
+        }
+    };
+    let notifier = async {
+        // Lets all the waiters queue themselves first.
+        tokio::task::yield_now().await;
+        assert_eq!(woken.get(), 0);
+        notify.notify_all();
+    };
+    tokio::join!(waiter(), waiter(), waiter(), notifier);
+    assert_eq!(woken.get(), 3);
+
+    // No permit is stored for the later waiters.
+    assert!(!notify.try_notified());
+}
+
+#[tokio::test]
+async fn test_notify_one_wakes_first_waiter() {
+    let notify = AsyncNotify::new();
+    let mut first = pin!(notify.notified());
+    let mut second = pin!(notify.notified());
+    let (first_flag, second_flag) = (Arc::new(Flag::default()), Arc::new(Flag::default()));
+    let (first_waker, second_waker) = (Waker::from(first_flag.clone()), Waker::from(second_flag.clone()));
+    assert!(first.as_mut().poll(&mut Context::from_waker(&first_waker)).is_pending());
+    assert!(second.as_mut().poll(&mut Context::from_waker(&second_waker)).is_pending());
+
+    notify.notify_one();
+    assert!(first_flag.0.load(Ordering::SeqCst));
+    assert!(!second_flag.0.load(Ordering::SeqCst));
+    assert!(first.as_mut().poll(&mut Context::from_waker(&first_waker)).is_ready());
+    assert!(second.as_mut().poll(&mut Context::from_waker(&second_waker)).is_pending());
+
+    notify.notify_one();
+    assert!(second_flag.0.load(Ordering::SeqCst));
+    assert!(second.as_mut().poll(&mut Context::from_waker(&second_waker)).is_ready());
+}
+
+#[tokio::test]
+async fn test_dropped_waiter_passes_permit_on() {
+    let notify = AsyncNotify::new();
+    let mut first = Box::pin(notify.notified());
+    let mut second = pin!(notify.notified());
+    let flag = Arc::new(Flag::default());
+    let waker = Waker::from(flag.clone());
+    assert!(first.as_mut().poll(&mut Context::from_waker(Waker::noop())).is_pending());
+    assert!(second.as_mut().poll(&mut Context::from_waker(&waker)).is_pending());
+
+    notify.notify_one();
+    drop(first);
+    assert!(flag.0.load(Ordering::SeqCst));
+    assert!(second.as_mut().poll(&mut Context::from_waker(&waker)).is_ready());
+}
@@ -0,0 +1,151 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+use std::{
+    cell::Cell,
+    future::Future,
+    pin::pin,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+use omango_sync::async_rwlock::{AsyncReadGuard, AsyncRwLock, AsyncWriteGuard};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn test_guard_auto_traits() {
+    assert_send_sync::<AsyncReadGuard<'static, i32>>();
+    assert_send_sync::<AsyncWriteGuard<'static, i32>>();
+}
+
+#[tokio::test]
+async fn test_shared_reads() {
+    let lock = AsyncRwLock::new(1);
+    let first = lock.read().await;
+    let second = lock.try_read().unwrap();
+    assert_eq!(*first + *second, 2);
+    assert!(lock.try_write().is_none());
+
+    drop(first);
+    drop(second);
+    *lock.write().await += 1;
+    assert_eq!(lock.into_inner(), 2);
+}
+
+#[tokio::test]
+async fn test_waiting_writer_blocks_new_readers() {
+    let lock = AsyncRwLock::new(());
+    let reader = lock.read().await;
+
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut write = pin!(lock.write());
+    assert!(write.as_mut().poll(&mut cx).is_pending());
+    // A reader arriving after the writer is queued behind it.
+    assert!(lock.try_read().is_none());
+    let mut read = pin!(lock.read());
+    assert!(read.as_mut().poll(&mut cx).is_pending());
+
+    drop(reader);
+    let writer = match write.as_mut().poll(&mut cx) {
+        Poll::Ready(writer) => writer,
+        Poll::Pending => panic!("the last reader must hand the lock over to the writer"),
+    };
+    assert!(read.as_mut().poll(&mut cx).is_pending());
+
+    drop(writer);
+    assert!(matches!(read.as_mut().poll(&mut cx), Poll::Ready(_)));
+}
+
+#[tokio::test]
+async fn test_writer_not_starved_by_readers() {
+    const READERS: usize = 4;
+    const MAX_READS: usize = 100_000;
+
+    let lock = AsyncRwLock::new(0);
+    let written = Cell::new(false);
+    let reads_before_write = Cell::new(0);
+    let reader = || {
+        let (lock, written, reads_before_write) = (&lock, &written, &reads_before_write);
+        async move {
+            // The readers overlap each other, so the lock is never free of readers on its own.
+            for _ in 0..MAX_READS {
+                if written.get() {
+                    return;
+                }
+                let _guard = lock.read().await;
+                reads_before_write.set(reads_before_write.get() + 1);
+                tokio::task::yield_now().await;
+            }
+        }
+    };
+    let writer = async {
+        tokio::task::yield_now().await;
+        *lock.write().await += 1;
+        written.set(true);
+    };
+    tokio::join!(reader(), reader(), reader(), reader(), writer);
+
+    assert!(written.get());
+    assert!(reads_before_write.get() < READERS * MAX_READS);
+    assert_eq!(*lock.read().await, 1);
+}
+
+#[test]
+fn test_writer_not_starved_across_threads() {
+    const READERS: usize = 3;
+
+    let lock = Arc::new(AsyncRwLock::new(0usize));
+    let stop = Arc::new(AtomicBool::new(false));
+    let active_readers = Arc::new(AtomicUsize::new(0));
+    thread::scope(|s| {
+        for _ in 0..READERS {
+            let (lock, stop, active_readers) = (lock.clone(), stop.clone(), active_readers.clone());
+            s.spawn(move || {
+                let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+                runtime.block_on(async {
+                    active_readers.fetch_add(1, Ordering::SeqCst);
+                    while !stop.load(Ordering::SeqCst) {
+                        let guard = lock.read().await;
+                        assert_eq!(*guard % 2, 0);
+                    }
+                });
+            });
+        }
+
+        while active_readers.load(Ordering::SeqCst) < READERS {
+            thread::yield_now();
+        }
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        runtime.block_on(async {
+            for _ in 0..100 {
+                let mut guard = lock.write().await;
+                // Readers never see the odd intermediate value.
+                *guard += 1;
+                *guard += 1;
+            }
+        });
+        stop.store(true, Ordering::SeqCst);
+    });
+    assert_eq!(*lock.try_read().unwrap(), 200);
+}
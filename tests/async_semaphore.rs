@@ -0,0 +1,119 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+use std::{
+    cell::Cell,
+    future::Future,
+    pin::pin,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    task::{Context, Poll, Wake, Waker},
+};
+
+use omango_sync::async_semaphore::AsyncSemaphore;
+
+/// Records whether the task was woken.
+#[derive(Default)]
+struct Flag(AtomicBool);
+
+impl Wake for Flag {
+    fn wake(self: Arc<Self>) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+// The tests run on a current-thread runtime: if a contended acquire blocked
+// the thread instead of suspending the task, the permit holder could never
+// run again and the test would hang.
+
+#[tokio::test]
+async fn test_try_acquire() {
+    let semaphore = AsyncSemaphore::new(2);
+    let first = semaphore.try_acquire().unwrap();
+    let _second = semaphore.try_acquire().unwrap();
+    assert!(semaphore.try_acquire().is_none());
+    assert_eq!(semaphore.available_permits(), 0);
+
+    drop(first);
+    assert_eq!(semaphore.available_permits(), 1);
+}
+
+#[tokio::test]
+async fn test_acquire_suspends_task() {
+    let semaphore = AsyncSemaphore::new(1);
+    let guard = semaphore.acquire().await;
+
+    let mut acquire = pin!(semaphore.acquire());
+    let mut cx = Context::from_waker(Waker::noop());
+    assert!(acquire.as_mut().poll(&mut cx).is_pending());
+    assert!(semaphore.has_waiters());
+
+    drop(guard);
+    assert!(matches!(acquire.as_mut().poll(&mut cx), Poll::Ready(_)));
+    assert!(!semaphore.has_waiters());
+}
+
+#[tokio::test]
+async fn test_contention_on_one_thread() {
+    const PERMITS: u32 = 2;
+
+    let semaphore = AsyncSemaphore::new(PERMITS);
+    let inside = Cell::new(0);
+    let max_inside = Cell::new(0);
+    let task = |rounds: usize| {
+        let (semaphore, inside, max_inside) = (&semaphore, &inside, &max_inside);
+        async move {
+            for _ in 0..rounds {
+                let _guard = semaphore.acquire().await;
+                inside.set(inside.get() + 1);
+                max_inside.set(max_inside.get().max(inside.get()));
+                // Lets the other tasks run while the permit is held.
+                tokio::task::yield_now().await;
+                inside.set(inside.get() - 1);
+            }
+        }
+    };
+    tokio::join!(task(100), task(100), task(100), task(100), task(100));
+
+    assert_eq!(max_inside.get(), PERMITS);
+    assert_eq!(semaphore.available_permits(), PERMITS);
+    assert!(!semaphore.has_waiters());
+}
+
+#[tokio::test]
+async fn test_dropped_acquire_passes_wakeup_on() {
+    let semaphore = AsyncSemaphore::new(1);
+    let guard = semaphore.acquire().await;
+
+    let mut first = Box::pin(semaphore.acquire());
+    let mut second = pin!(semaphore.acquire());
+    let flag = Arc::new(Flag::default());
+    let waker = Waker::from(flag.clone());
+    assert!(first.as_mut().poll(&mut Context::from_waker(Waker::noop())).is_pending());
+    assert!(second.as_mut().poll(&mut Context::from_waker(&waker)).is_pending());
+
+    // The first task is woken for the permit but gives up, the second one is woken instead.
+    drop(guard);
+    assert!(!flag.0.load(Ordering::SeqCst));
+    drop(first);
+    assert!(flag.0.load(Ordering::SeqCst));
+    assert!(matches!(second.as_mut().poll(&mut Context::from_waker(&waker)), Poll::Ready(_)));
+}
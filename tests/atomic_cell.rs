@@ -0,0 +1,92 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+};
+
+use omango_sync::atomic_cell::AtomicCell;
+
+const THREADS: u64 = 4;
+const SWAPS: u64 = 5_000;
+
+/// Swaps unique values in from several threads while others load,
+/// every value must come out exactly once.
+fn check_swaps<T, F>(make: F, check: fn(&T) -> bool)
+where
+    T: Clone + Send + Ord + std::fmt::Debug,
+    F: Fn(u64) -> T + Sync,
+{
+    let cell = AtomicCell::new(make(0));
+    let done = AtomicBool::new(false);
+    let mut out = thread::scope(|scope| {
+        for _ in 0..2 {
+            scope.spawn(|| {
+                while !done.load(Ordering::Relaxed) {
+                    assert!(check(&cell.load()));
+                }
+            });
+        }
+        let handles: Vec<_> = (0..THREADS)
+            .map(|id| {
+                let (cell, make) = (&cell, &make);
+                scope.spawn(move || {
+                    (0..SWAPS)
+                        .map(|i| cell.swap(make(1 + id * SWAPS + i)))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        let out: Vec<_> = handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect();
+        done.store(true, Ordering::Relaxed);
+        out
+    });
+
+    out.push(cell.into_inner());
+    out.sort();
+    let mut expected: Vec<_> = (0..=THREADS * SWAPS).map(make).collect();
+    expected.sort();
+    assert_eq!(out, expected);
+}
+
+#[test]
+fn test_lock_free_swap() {
+    assert!(AtomicCell::<u64>::is_lock_free());
+    check_swaps(|value| value, |value| *value <= THREADS * SWAPS);
+}
+
+#[test]
+fn test_locked_swap() {
+    assert!(!AtomicCell::<String>::is_lock_free());
+    check_swaps(|value| value.to_string().repeat(3), |value| {
+        let third = &value[..value.len() / 3];
+        *value == third.repeat(3)
+    });
+}
+
+#[test]
+fn test_take() {
+    let cell = AtomicCell::new(Some(String::from("a")));
+    assert_eq!(cell.take().as_deref(), Some("a"));
+    assert_eq!(cell.take(), None);
+    cell.store(Some(String::from("b")));
+    assert_eq!(cell.load().as_deref(), Some("b"));
+}
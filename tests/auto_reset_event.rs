@@ -0,0 +1,89 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{sync::Arc, thread, time::Duration};
+
+use omango_sync::auto_reset_event::AutoResetEvent;
+
+#[test]
+fn test_set_before_wait() {
+    let event = AutoResetEvent::new(false);
+    event.set();
+    assert!(event.is_set());
+    event.wait();
+    // The wait consumed the event.
+    assert!(!event.is_set());
+    assert!(!event.try_wait());
+}
+
+#[test]
+fn test_set_after_wait() {
+    let event = Arc::new(AutoResetEvent::new(false));
+    let event_clone = event.clone();
+    let handle = thread::spawn(move || event_clone.wait());
+
+    thread::sleep(Duration::from_millis(30));
+    assert!(!handle.is_finished());
+    event.set();
+    handle.join().unwrap();
+    assert!(!event.is_set());
+}
+
+#[test]
+fn test_second_wait_blocks() {
+    let event = Arc::new(AutoResetEvent::new(true));
+    event.wait();
+
+    let event_clone = event.clone();
+    let handle = thread::spawn(move || event_clone.wait());
+    thread::sleep(Duration::from_millis(30));
+    assert!(!handle.is_finished());
+
+    event.set();
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_one_waiter_per_set() {
+    let event = Arc::new(AutoResetEvent::new(false));
+    let handles: Vec<_> = (0..2)
+        .map(|_| {
+            let event = event.clone();
+            thread::spawn(move || event.wait())
+        })
+        .collect();
+
+    thread::sleep(Duration::from_millis(30));
+    event.set();
+    thread::sleep(Duration::from_millis(30));
+    assert_eq!(handles.iter().filter(|handle| handle.is_finished()).count(), 1);
+
+    event.set();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+fn test_reset() {
+    let event = AutoResetEvent::new(true);
+    event.reset();
+    assert!(!event.try_wait());
+}
@@ -0,0 +1,55 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use omango_sync::backoff::Backoff;
+
+#[test]
+fn test_snooze_completed() {
+    let mut backoff = Backoff::new();
+    let mut calls = 0;
+    while !backoff.snooze_completed() {
+        calls += 1;
+    }
+    assert_eq!(calls, 11);
+
+    backoff.reset();
+    assert!(!backoff.snooze_completed());
+}
+
+#[test]
+fn test_pause_and_snooze_never_sleep() {
+    let mut backoff = Backoff::new();
+    for _ in 0..1_000 {
+        backoff.pause();
+        backoff.snooze();
+    }
+    // The sleep never started growing.
+    assert!(!backoff.is_completed());
+}
+
+#[test]
+fn test_spin_completes() {
+    let mut backoff = Backoff::new();
+    while !backoff.is_completed() {
+        backoff.spin();
+    }
+    backoff.reset();
+    assert!(!backoff.is_completed());
+}
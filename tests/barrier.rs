@@ -0,0 +1,95 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+use omango_sync::barrier::Barrier;
+
+#[test]
+fn test_single_party() {
+    let barrier = Barrier::new(1);
+    assert!(barrier.wait().is_leader());
+    assert!(barrier.wait().is_leader());
+}
+
+#[test]
+fn test_blocks_until_all_arrive() {
+    let barrier = Arc::new(Barrier::new(3));
+    let passed = Arc::new(AtomicU32::new(0));
+
+    let handles: Vec<_> = (0..2)
+        .map(|_| {
+            let barrier = barrier.clone();
+            let passed = passed.clone();
+            thread::spawn(move || {
+                barrier.wait();
+                passed.fetch_add(1, Ordering::SeqCst);
+            })
+        })
+        .collect();
+
+    thread::sleep(Duration::from_millis(50));
+    assert_eq!(passed.load(Ordering::SeqCst), 0);
+
+    barrier.wait();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(passed.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn test_one_leader_per_cycle() {
+    const THREADS: u32 = 8;
+    const CYCLES: u32 = 100;
+
+    let barrier = Arc::new(Barrier::new(THREADS));
+    let leaders = Arc::new(AtomicU32::new(0));
+    let arrived = Arc::new(AtomicU32::new(0));
+
+    let handles: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let barrier = barrier.clone();
+            let leaders = leaders.clone();
+            let arrived = arrived.clone();
+            thread::spawn(move || {
+                for cycle in 1..=CYCLES {
+                    arrived.fetch_add(1, Ordering::SeqCst);
+                    if barrier.wait().is_leader() {
+                        leaders.fetch_add(1, Ordering::SeqCst);
+                    }
+                    // Nobody leaves a cycle before all threads have arrived in it.
+                    assert!(arrived.load(Ordering::SeqCst) >= cycle * THREADS);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(leaders.load(Ordering::SeqCst), CYCLES);
+}
@@ -0,0 +1,152 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::Duration,
+};
+
+use omango_sync::blocking_queue::BlockingQueue;
+
+#[test]
+fn test_fifo() {
+    let queue = BlockingQueue::new();
+    for i in 0..3 {
+        queue.push(i);
+    }
+    assert_eq!(queue.len(), 3);
+    assert_eq!(queue.capacity(), None);
+    assert_eq!(queue.pop(), 0);
+    assert_eq!(queue.try_pop(), Some(1));
+    assert_eq!(queue.pop_timeout(Duration::from_millis(1)), Some(2));
+    assert!(queue.is_empty());
+    assert_eq!(queue.try_pop(), None);
+}
+
+#[test]
+fn test_pop_timeout() {
+    let queue = BlockingQueue::<i32>::new();
+    assert_eq!(queue.pop_timeout(Duration::from_millis(20)), None);
+
+    thread::scope(|s| {
+        let consumer = s.spawn(|| queue.pop_timeout(Duration::from_secs(10)));
+        thread::sleep(Duration::from_millis(20));
+        queue.push(7);
+        assert_eq!(consumer.join().unwrap(), Some(7));
+    });
+}
+
+#[test]
+fn test_pop_blocks_until_push() {
+    let queue = BlockingQueue::new();
+    thread::scope(|s| {
+        let consumer = s.spawn(|| queue.pop());
+        thread::sleep(Duration::from_millis(50));
+        assert!(!consumer.is_finished());
+        queue.push(1);
+        assert_eq!(consumer.join().unwrap(), 1);
+    });
+}
+
+#[test]
+fn test_drain() {
+    let queue = BlockingQueue::new();
+    queue.push(1);
+    queue.push(2);
+    assert_eq!(queue.drain(), vec![1, 2]);
+    assert!(queue.is_empty());
+    assert!(queue.drain().is_empty());
+}
+
+#[test]
+fn test_bounded_backpressure() {
+    let queue = BlockingQueue::bounded(2);
+    assert_eq!(queue.capacity(), Some(2));
+    queue.push(1);
+    queue.push(2);
+
+    let pushed = AtomicBool::new(false);
+    thread::scope(|s| {
+        s.spawn(|| {
+            queue.push(3);
+            pushed.store(true, Ordering::SeqCst);
+        });
+        thread::sleep(Duration::from_millis(50));
+        assert!(!pushed.load(Ordering::SeqCst));
+
+        // Draining makes room for the blocked producer.
+        assert_eq!(queue.drain(), vec![1, 2]);
+    });
+    assert!(pushed.into_inner());
+    assert_eq!(queue.pop(), 3);
+}
+
+#[test]
+fn test_two_producers_three_consumers() {
+    const PRODUCERS: usize = 2;
+    const CONSUMERS: usize = 3;
+    const ITEMS: usize = 50_000;
+
+    let queue = BlockingQueue::bounded(64);
+    let mut received: Vec<(usize, usize)> = thread::scope(|s| {
+        let consumers: Vec<_> = (0..CONSUMERS)
+            .map(|_| {
+                s.spawn(|| {
+                    let mut received = Vec::new();
+                    let mut last = [None; PRODUCERS];
+                    // `None` tells the consumer to stop.
+                    while let Some((producer, i)) = queue.pop() {
+                        // The items of one producer are received in order.
+                        assert!(last[producer] < Some(i));
+                        last[producer] = Some(i);
+                        received.push((producer, i));
+                    }
+                    received
+                })
+            })
+            .collect();
+
+        let producers: Vec<_> = (0..PRODUCERS)
+            .map(|producer| {
+                let queue = &queue;
+                s.spawn(move || {
+                    for i in 0..ITEMS {
+                        queue.push(Some((producer, i)));
+                    }
+                })
+            })
+            .collect();
+        for producer in producers {
+            producer.join().unwrap();
+        }
+        for _ in 0..CONSUMERS {
+            queue.push(None);
+        }
+        consumers.into_iter().flat_map(|consumer| consumer.join().unwrap()).collect()
+    });
+
+    // No item is lost or received twice.
+    received.sort_unstable();
+    let expected: Vec<_> = (0..PRODUCERS)
+        .flat_map(|producer| (0..ITEMS).map(move |i| (producer, i)))
+        .collect();
+    assert_eq!(received, expected);
+    assert!(queue.is_empty());
+}
@@ -0,0 +1,107 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+use omango_sync::broadcast_barrier::BroadcastBarrier;
+
+const WAITERS: usize = 8;
+
+#[test]
+fn test_register() {
+    let barrier = BroadcastBarrier::new();
+    let waiter = barrier.register();
+    let other = barrier.register();
+    assert_eq!(barrier.registered(), 2);
+
+    drop(waiter);
+    drop(other);
+    assert_eq!(barrier.registered(), 0);
+}
+
+#[test]
+fn test_one_broadcast_releases_all() {
+    let barrier = BroadcastBarrier::new();
+    let released = AtomicUsize::new(0);
+    thread::scope(|s| {
+        for _ in 0..WAITERS {
+            let waiter = barrier.register();
+            let released = &released;
+            s.spawn(move || {
+                waiter.wait();
+                released.fetch_add(1, Ordering::SeqCst);
+            });
+        }
+
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(released.load(Ordering::SeqCst), 0);
+        barrier.broadcast();
+    });
+    assert_eq!(released.into_inner(), WAITERS);
+}
+
+#[test]
+fn test_next_cycle() {
+    let barrier = BroadcastBarrier::new();
+    let released = AtomicUsize::new(0);
+    thread::scope(|s| {
+        for _ in 0..WAITERS {
+            let waiter = barrier.register();
+            let released = &released;
+            s.spawn(move || {
+                for _ in 0..2 {
+                    waiter.wait();
+                    released.fetch_add(1, Ordering::SeqCst);
+                }
+            });
+        }
+
+        barrier.broadcast();
+        while released.load(Ordering::SeqCst) < WAITERS {
+            thread::yield_now();
+        }
+
+        // All waiters proceeded and wait again, the second cycle blocks them.
+        thread::sleep(Duration::from_millis(50));
+        assert_eq!(released.load(Ordering::SeqCst), WAITERS);
+        barrier.broadcast();
+    });
+    assert_eq!(released.into_inner(), 2 * WAITERS);
+}
+
+#[test]
+fn test_missed_broadcasts_coalesce() {
+    let barrier = BroadcastBarrier::new();
+    let waiter = barrier.register();
+    barrier.broadcast();
+    barrier.broadcast();
+
+    // The missed broadcasts release one wait only.
+    waiter.wait();
+    thread::scope(|s| {
+        let handle = s.spawn(move || waiter.wait());
+        thread::sleep(Duration::from_millis(50));
+        assert!(!handle.is_finished());
+        barrier.broadcast();
+    });
+}
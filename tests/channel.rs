@@ -0,0 +1,87 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::thread;
+
+use omango_sync::channel::bounded;
+
+#[test]
+fn test_try_send_recv() {
+    let (sender, receiver) = bounded(2);
+    assert_eq!(sender.try_send(1), Ok(()));
+    assert_eq!(sender.try_send(2), Ok(()));
+    assert_eq!(sender.try_send(3), Err(3));
+    assert_eq!(receiver.try_recv(), Some(1));
+    assert_eq!(receiver.try_recv(), Some(2));
+    assert_eq!(receiver.try_recv(), None);
+}
+
+#[test]
+fn test_drain_after_senders_dropped() {
+    let (sender, receiver) = bounded(4);
+    let other = sender.clone();
+    sender.send(1).unwrap();
+    other.send(2).unwrap();
+    drop(sender);
+    drop(other);
+    assert_eq!(receiver.recv(), Some(1));
+    assert_eq!(receiver.recv(), Some(2));
+    assert_eq!(receiver.recv(), None);
+}
+
+#[test]
+fn test_send_after_receiver_dropped() {
+    let (sender, receiver) = bounded(1);
+    drop(receiver);
+    assert_eq!(sender.send(1), Err(1));
+}
+
+#[test]
+fn test_multi_producer() {
+    const PRODUCERS: u64 = 4;
+    const ITEMS: u64 = 10_000;
+
+    let (sender, receiver) = bounded(16);
+    let handles: Vec<_> = (0..PRODUCERS)
+        .map(|id| {
+            let sender = sender.clone();
+            thread::spawn(move || {
+                for item in 0..ITEMS {
+                    sender.send(id * ITEMS + item).unwrap();
+                }
+            })
+        })
+        .collect();
+    drop(sender);
+
+    let mut last = vec![None; PRODUCERS as usize];
+    let mut count = 0;
+    while let Some(value) = receiver.recv() {
+        // The items of one producer arrive in order.
+        let id = (value / ITEMS) as usize;
+        assert!(last[id] < Some(value));
+        last[id] = Some(value);
+        count += 1;
+    }
+    assert_eq!(count, PRODUCERS * ITEMS);
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
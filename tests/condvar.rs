@@ -0,0 +1,112 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    collections::VecDeque,
+    sync::Arc,
+    thread,
+    time::{Duration, Instant},
+};
+
+use omango_sync::{condvar::CondVar, mutex::Mutex};
+
+#[test]
+fn test_wait_timeout() {
+    let mutex = Mutex::new(());
+    let condvar = CondVar::new();
+
+    let start = Instant::now();
+    let (_guard, timed_out) = condvar.wait_timeout(mutex.lock(), Duration::from_millis(50));
+    assert!(timed_out);
+    assert!(start.elapsed() >= Duration::from_millis(50));
+}
+
+#[test]
+fn test_notify_one() {
+    let pair = Arc::new((Mutex::new(false), CondVar::new()));
+    let pair_clone = pair.clone();
+    let handle = thread::spawn(move || {
+        let (mutex, condvar) = &*pair_clone;
+        let mut ready = mutex.lock();
+        while !*ready {
+            ready = condvar.wait(ready);
+        }
+    });
+
+    thread::sleep(Duration::from_millis(20));
+    *pair.0.lock() = true;
+    pair.1.notify_one();
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_producer_consumer() {
+    const PRODUCERS: i32 = 4;
+    const CONSUMERS: usize = 4;
+    const ITEMS: i32 = 2_500;
+
+    let shared = Arc::new((Mutex::new(VecDeque::new()), CondVar::new()));
+    let consumers: Vec<_> = (0..CONSUMERS)
+        .map(|_| {
+            let shared = shared.clone();
+            thread::spawn(move || {
+                let (queue, condvar) = &*shared;
+                let mut sum = 0i64;
+                loop {
+                    let mut guard = queue.lock();
+                    while guard.is_empty() {
+                        guard = condvar.wait(guard);
+                    }
+                    match guard.pop_front().unwrap() {
+                        -1 => return sum,
+                        value => sum += value as i64,
+                    }
+                }
+            })
+        })
+        .collect();
+    let producers: Vec<_> = (0..PRODUCERS)
+        .map(|_| {
+            let shared = shared.clone();
+            thread::spawn(move || {
+                let (queue, condvar) = &*shared;
+                for value in 1..=ITEMS {
+                    queue.lock().push_back(value);
+                    condvar.notify_one();
+                }
+            })
+        })
+        .collect();
+    for producer in producers {
+        producer.join().unwrap();
+    }
+
+    {
+        let (queue, condvar) = &*shared;
+        let mut guard = queue.lock();
+        for _ in 0..CONSUMERS {
+            guard.push_back(-1);
+        }
+        drop(guard);
+        condvar.notify_all();
+    }
+    let total: i64 = consumers.into_iter().map(|consumer| consumer.join().unwrap()).sum();
+    assert_eq!(total, PRODUCERS as i64 * (ITEMS as i64 * (ITEMS as i64 + 1) / 2));
+}
@@ -0,0 +1,75 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    },
+    thread,
+};
+
+use omango_sync::cyclic_barrier::CyclicBarrier;
+
+static ACTIONS: AtomicU32 = AtomicU32::new(0);
+
+fn count_action() {
+    ACTIONS.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+fn test_action_once_per_cycle() {
+    const THREADS: u32 = 4;
+    const CYCLES: u32 = 10;
+
+    let barrier = Arc::new(CyclicBarrier::new(THREADS, Some(count_action)));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|_| {
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                let mut leaders = 0;
+                for cycle in 0..CYCLES {
+                    let result = barrier.await_cycle();
+                    assert_eq!(result.generation(), cycle);
+                    // The action of this cycle has completed before anyone is released.
+                    assert!(ACTIONS.load(Ordering::SeqCst) > cycle);
+                    if result.is_leader() {
+                        leaders += 1;
+                    }
+                }
+                leaders
+            })
+        })
+        .collect();
+    let leaders: u32 = handles.into_iter().map(|handle| handle.join().unwrap()).sum();
+
+    assert_eq!(leaders, CYCLES);
+    assert_eq!(ACTIONS.load(Ordering::SeqCst), CYCLES);
+    assert_eq!(barrier.generation(), CYCLES);
+}
+
+#[test]
+fn test_without_action() {
+    let barrier = Arc::new(CyclicBarrier::new(2, None));
+    let barrier_clone = barrier.clone();
+    let handle = thread::spawn(move || barrier_clone.await_cycle().is_leader());
+    let leader = barrier.await_cycle().is_leader();
+    assert!(leader ^ handle.join().unwrap());
+}
@@ -0,0 +1,144 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+use omango_sync::deadlock::{DeadlockCycle, DeadlockDetector};
+
+// The graph is global and shared by the tests running in parallel, so every test
+// uses its own identifiers, far from the addresses used for the real threads and locks.
+fn cycles_of(threads: &[usize]) -> Vec<DeadlockCycle> {
+    DeadlockDetector::check()
+        .into_iter()
+        .filter(|cycle| cycle.threads.iter().any(|thread| threads.contains(thread)))
+        .collect()
+}
+
+#[test]
+fn test_two_threads_cycle() {
+    let (t1, t2) = (1001, 1002);
+    let (l1, l2) = (2001, 2002);
+    DeadlockDetector::acquired(t1, l1);
+    DeadlockDetector::acquired(t2, l2);
+    DeadlockDetector::acquire_attempt(t1, l2);
+    assert!(cycles_of(&[t1, t2]).is_empty());
+
+    DeadlockDetector::acquire_attempt(t2, l1);
+    let cycles = cycles_of(&[t1, t2]);
+    assert_eq!(cycles.len(), 1);
+    let cycle = &cycles[0];
+    let mut threads = cycle.threads.clone();
+    threads.sort_unstable();
+    assert_eq!(threads, vec![t1, t2]);
+    // Each thread waits for the lock held by the next one.
+    for (thread, lock) in cycle.threads.iter().zip(&cycle.locks) {
+        let expected = if *thread == t1 { l2 } else { l1 };
+        assert_eq!(*lock, expected);
+    }
+
+    // Releasing a lock breaks the cycle.
+    DeadlockDetector::released(l1);
+    assert!(cycles_of(&[t1, t2]).is_empty());
+    DeadlockDetector::acquired(t2, l1);
+    DeadlockDetector::released(l1);
+    DeadlockDetector::released(l2);
+}
+
+#[test]
+fn test_chain_is_not_a_cycle() {
+    let threads = [1101, 1102, 1103];
+    let locks = [2101, 2102, 2103];
+    for (&thread, &lock) in threads.iter().zip(&locks) {
+        DeadlockDetector::acquired(thread, lock);
+    }
+    DeadlockDetector::acquire_attempt(threads[0], locks[1]);
+    DeadlockDetector::acquire_attempt(threads[1], locks[2]);
+    assert!(cycles_of(&threads).is_empty());
+
+    // The last thread closes the cycle through all three.
+    DeadlockDetector::acquire_attempt(threads[2], locks[0]);
+    let cycles = cycles_of(&threads);
+    assert_eq!(cycles.len(), 1);
+    assert_eq!(cycles[0].threads.len(), 3);
+
+    for &thread in &threads {
+        DeadlockDetector::acquired(thread, 2199);
+    }
+    for &lock in locks.iter().chain(&[2199]) {
+        DeadlockDetector::released(lock);
+    }
+}
+
+#[test]
+fn test_self_loop() {
+    let (thread, lock) = (1201, 2201);
+    DeadlockDetector::acquired(thread, lock);
+    DeadlockDetector::acquire_attempt(thread, lock);
+    assert_eq!(
+        cycles_of(&[thread]),
+        vec![DeadlockCycle { threads: vec![thread], locks: vec![lock] }],
+    );
+
+    DeadlockDetector::acquired(thread, lock);
+    DeadlockDetector::released(lock);
+    assert!(cycles_of(&[thread]).is_empty());
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_mutex_reports_deadlock() {
+    use std::{
+        sync::{Arc, Barrier, mpsc},
+        thread,
+        time::Duration,
+    };
+
+    use omango_sync::mutex::Mutex;
+
+    let first = Arc::new(Mutex::new(()));
+    let second = Arc::new(Mutex::new(()));
+    let barrier = Arc::new(Barrier::new(2));
+    let (sender, receiver) = mpsc::channel();
+
+    // The threads really deadlock, they are never joined and end with the test process.
+    for (held, wanted) in [(first.clone(), second.clone()), (second, first)] {
+        let (barrier, sender) = (barrier.clone(), sender.clone());
+        thread::spawn(move || {
+            DeadlockDetector::register_current_thread();
+            sender.send(DeadlockDetector::current_thread_id()).unwrap();
+            let _held = held.lock();
+            barrier.wait();
+            let _wanted = wanted.lock();
+        });
+    }
+    let threads = [receiver.recv().unwrap(), receiver.recv().unwrap()];
+
+    let mut cycles = cycles_of(&threads);
+    for _ in 0..500 {
+        if !cycles.is_empty() {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+        cycles = cycles_of(&threads);
+    }
+    assert_eq!(cycles.len(), 1);
+    let mut found = cycles[0].threads.clone();
+    found.sort_unstable();
+    let mut expected = threads.to_vec();
+    expected.sort_unstable();
+    assert_eq!(found, expected);
+}
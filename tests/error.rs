@@ -0,0 +1,189 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+use std::{
+    collections::HashSet,
+    error::Error as _,
+    fmt, io, panic,
+    time::Duration,
+};
+
+use omango_sync::error::{Error, ErrorKind, ErrorVec};
+
+#[test]
+fn test_kind() {
+    assert_eq!(Error::new(ErrorKind::UserError, "misuse").kind(), ErrorKind::UserError);
+    assert_eq!(Error::new(ErrorKind::InternalError, "bug").kind(), ErrorKind::InternalError);
+    assert_eq!(Error::panic("boom").kind(), ErrorKind::Panic);
+    assert_eq!(Error::timeout("too slow").kind(), ErrorKind::Timeout);
+
+    let cancelled = Error::cancelled();
+    assert_eq!(cancelled.kind(), ErrorKind::Cancelled);
+    assert_eq!(cancelled.message, "operation cancelled");
+}
+
+#[test]
+fn test_eq_compares_kind_and_message() {
+    assert_eq!(Error::panic("boom"), Error::new(ErrorKind::Panic, "boom"));
+    assert_ne!(Error::panic("boom"), Error::timeout("boom"));
+    assert_ne!(Error::panic("boom"), Error::panic("bang"));
+}
+
+#[test]
+fn test_wrap_keeps_source() {
+    let error = Error::wrap("formatting failed", fmt::Error);
+    assert_eq!(error.kind(), ErrorKind::UserError);
+    assert_eq!(error.to_string(), "formatting failed");
+
+    let source = error.source().unwrap();
+    assert!(source.is::<fmt::Error>());
+    assert!(source.source().is_none());
+}
+
+#[test]
+fn test_no_source() {
+    assert!(Error::panic("boom").source().is_none());
+}
+
+#[test]
+fn test_from_panic_payload() {
+    let literal = panic::catch_unwind(|| panic!("literal message")).unwrap_err();
+    let error = Error::from(literal);
+    assert!(error.is_panic());
+    assert_eq!(error.message, "literal message");
+
+    let value = 42;
+    let formatted = panic::catch_unwind(|| panic!("formatted {}", value)).unwrap_err();
+    assert_eq!(Error::from(formatted).message, "formatted 42");
+
+    let opaque = panic::catch_unwind(|| panic::panic_any(7)).unwrap_err();
+    assert_eq!(Error::from(opaque).message, "<opaque panic payload>");
+}
+
+#[test]
+fn test_context() {
+    let error = Error::timeout("no response");
+    assert!(error.context_map().is_empty());
+
+    let error = error.with_context("key", "user:42").with_context("attempt", "3");
+    assert_eq!(error.context_map().get("key").map(String::as_str), Some("user:42"));
+    assert_eq!(error.context_map().len(), 2);
+    // The entries are displayed sorted by key.
+    assert_eq!(error.to_string(), "no response [attempt=3, key=user:42]");
+
+    // The context is not compared.
+    assert_eq!(error, Error::timeout("no response"));
+}
+
+#[test]
+fn test_hash_set() {
+    let mut set = HashSet::new();
+    set.insert(Error::panic("boom"));
+    // Equal to the first one, even with a context, so it has the same hash.
+    set.insert(Error::panic("boom").with_context("key", "1"));
+    assert_eq!(set.len(), 1);
+
+    set.insert(Error::timeout("boom"));
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn test_error_vec() {
+    let mut errors = ErrorVec::default();
+    assert!(errors.is_empty());
+    errors.push(Error::timeout("first"));
+    errors.push(Error::timeout("second"));
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors.to_string(), "1. first\n2. second");
+
+    // The errors share the kind, so it is kept.
+    assert_eq!(Error::from(errors), Error::timeout("first; second"));
+
+    let mixed = ErrorVec::from(vec![Error::panic("boom"), Error::cancelled()]);
+    assert_eq!(Error::from(mixed), Error::new(ErrorKind::UserError, "boom; operation cancelled"));
+}
+
+#[test]
+fn test_hint() {
+    let error = Error::panic("boom");
+    assert_eq!(error.hint(), None);
+
+    let error = error
+        .with_hint("ensure the function does not panic")
+        .with_context("key", "1");
+    assert_eq!(error.hint(), Some("ensure the function does not panic"));
+    assert_eq!(error.to_string(), "boom [key=1] (Hint: ensure the function does not panic)");
+}
+
+#[test]
+fn test_predicates() {
+    let panic = Error::panic("boom");
+    assert!(panic.is_panic() && !panic.is_timeout() && !panic.is_cancelled());
+
+    let timeout = Error::timeout("too slow");
+    assert!(!timeout.is_panic() && timeout.is_timeout() && !timeout.is_cancelled());
+
+    let cancelled = Error::cancelled();
+    assert!(!cancelled.is_panic() && !cancelled.is_timeout() && cancelled.is_cancelled());
+
+    let user = Error::new(ErrorKind::UserError, "misuse");
+    assert!(!user.is_panic() && !user.is_timeout() && !user.is_cancelled());
+}
+
+#[test]
+fn test_timeout_after() {
+    let error = Error::timeout_after(Duration::from_millis(250), "exec timed out");
+    assert!(error.is_timeout());
+    assert_eq!(error.timed_out_after(), Some(Duration::from_millis(250)));
+    assert_eq!(error.to_string(), "exec timed out");
+
+    assert_eq!(Error::timeout("no duration").timed_out_after(), None);
+}
+
+#[test]
+fn test_into_source_round_trip() {
+    let original = io::Error::new(io::ErrorKind::NotFound, "config.toml");
+    let error = Error::wrap("failed to load the config", original);
+
+    let recovered = error.into_source().unwrap().downcast::<io::Error>().unwrap();
+    assert_eq!(recovered.kind(), io::ErrorKind::NotFound);
+    assert_eq!(recovered.to_string(), "config.toml");
+
+    assert!(Error::panic("boom").into_source().is_none());
+}
+
+#[test]
+fn test_context_chain() {
+    let error = Error::timeout("socket read timed out")
+        .context("while fetching the user")
+        .context("while loading user config");
+    // The annotations keep the kind of the root cause.
+    assert!(error.is_timeout());
+
+    let mut messages = Vec::new();
+    let mut next: Option<&(dyn std::error::Error + 'static)> = Some(&error);
+    while let Some(current) = next {
+        messages.push(current.to_string());
+        next = current.source();
+    }
+    assert_eq!(
+        messages,
+        ["while loading user config", "while fetching the user", "socket read timed out"],
+    );
+}
@@ -0,0 +1,63 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{sync::Arc, thread, time::Duration};
+
+use omango_sync::event::Event;
+
+#[test]
+fn test_wakes_blocked_waiters() {
+    let event = Arc::new(Event::new());
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let event = event.clone();
+            thread::spawn(move || event.wait())
+        })
+        .collect();
+
+    thread::sleep(Duration::from_millis(50));
+    assert!(handles.iter().all(|handle| !handle.is_finished()));
+    assert!(!event.is_signaled());
+
+    event.signal();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+fn test_signal_before_wait() {
+    let event = Arc::new(Event::new());
+    event.signal();
+    // Signaling again is a no-op.
+    event.signal();
+    assert!(event.is_signaled());
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let event = event.clone();
+            thread::spawn(move || event.wait())
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    event.wait();
+}
@@ -0,0 +1,64 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{sync::Arc, thread, time::Duration};
+
+use omango_sync::exchanger::Exchanger;
+
+#[test]
+fn test_swap_values() {
+    let exchanger = Arc::new(Exchanger::new());
+    let exchanger_clone = exchanger.clone();
+    let handle = thread::spawn(move || exchanger_clone.exchange(String::from("a")));
+
+    assert_eq!(exchanger.exchange(String::from("b")), "a");
+    assert_eq!(handle.join().unwrap(), "b");
+}
+
+#[test]
+fn test_lone_thread_blocks() {
+    let exchanger = Arc::new(Exchanger::new());
+    let exchanger_clone = exchanger.clone();
+    let handle = thread::spawn(move || exchanger_clone.exchange(1));
+
+    thread::sleep(Duration::from_millis(50));
+    assert!(!handle.is_finished());
+
+    assert_eq!(exchanger.exchange(2), 1);
+    assert_eq!(handle.join().unwrap(), 2);
+}
+
+#[test]
+fn test_many_rounds() {
+    const ROUNDS: u64 = 10_000;
+
+    let exchanger = Arc::new(Exchanger::new());
+    let exchanger_clone = exchanger.clone();
+    let handle = thread::spawn(move || {
+        for round in 0..ROUNDS {
+            assert_eq!(exchanger_clone.exchange(round * 2), round * 2 + 1);
+        }
+    });
+
+    for round in 0..ROUNDS {
+        assert_eq!(exchanger.exchange(round * 2 + 1), round * 2);
+    }
+    handle.join().unwrap();
+}
@@ -0,0 +1,97 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{cell::Cell, sync::Arc, thread, time::Duration};
+
+use omango_sync::fair_mutex::{FairMutex, FairMutexGuard};
+
+// Compiles only if the type does not implement the trait,
+// otherwise the call is ambiguous between the two impls.
+macro_rules! assert_not_impl {
+    ($ty:ty: $tr:path) => {{
+        trait AmbiguousIfImpl<A> {
+            fn some_item() {}
+        }
+        impl<T: ?Sized> AmbiguousIfImpl<()> for T {}
+        impl<T: ?Sized + $tr> AmbiguousIfImpl<u8> for T {}
+        <$ty as AmbiguousIfImpl<_>>::some_item()
+    }};
+}
+
+fn assert_sync<T: Sync>() {}
+
+#[test]
+fn test_guard_auto_traits() {
+    assert_sync::<FairMutexGuard<'static, i32>>();
+    assert_not_impl!(FairMutexGuard<'static, i32>: Send);
+    assert_not_impl!(FairMutexGuard<'static, Cell<i32>>: Sync);
+}
+
+#[test]
+fn test_try_lock() {
+    let mutex = FairMutex::new(0);
+    let guard = mutex.try_lock().unwrap();
+    assert!(mutex.try_lock().is_none());
+    drop(guard);
+    *mutex.try_lock().unwrap() += 1;
+    assert_eq!(mutex.into_inner(), 1);
+}
+
+#[test]
+fn test_fifo_order() {
+    const THREADS: usize = 6;
+
+    let mutex = Arc::new(FairMutex::new(Vec::new()));
+    let guard = mutex.lock();
+    let handles: Vec<_> = (0..THREADS)
+        .map(|id| {
+            let mutex = mutex.clone();
+            let handle = thread::spawn(move || mutex.lock().push(id));
+            // Gives the thread time to join the queue before the next one.
+            thread::sleep(Duration::from_millis(30));
+            handle
+        })
+        .collect();
+
+    drop(guard);
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(*mutex.lock(), (0..THREADS).collect::<Vec<_>>());
+}
+
+#[test]
+fn test_contention() {
+    let mutex = Arc::new(FairMutex::new(0u64));
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let mutex = mutex.clone();
+            thread::spawn(move || {
+                for _ in 0..5_000 {
+                    *mutex.lock() += 1;
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(*mutex.lock(), 40_000);
+}
@@ -0,0 +1,104 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU32, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+use omango_sync::fair_semaphore::FairSemaphore;
+
+#[test]
+fn test_try_acquire() {
+    let semaphore = FairSemaphore::new(2);
+    assert!(semaphore.try_acquire());
+    assert!(semaphore.try_acquire());
+    assert!(!semaphore.try_acquire());
+    assert_eq!(semaphore.available_permits(), 0);
+
+    semaphore.release();
+    assert_eq!(semaphore.available_permits(), 1);
+    assert!(!semaphore.has_waiters());
+}
+
+#[test]
+fn test_guard_releases() {
+    let semaphore = FairSemaphore::new(1);
+    let guard = semaphore.acquire_guard();
+    assert!(!semaphore.try_acquire());
+    drop(guard);
+    assert_eq!(semaphore.available_permits(), 1);
+}
+
+#[test]
+fn test_fifo_order() {
+    const THREADS: usize = 8;
+
+    let semaphore = Arc::new(FairSemaphore::new(0));
+    let called = Arc::new(Mutex::new(Vec::new()));
+    let acquired = Arc::new(Mutex::new(Vec::new()));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|id| {
+            let (semaphore, called, acquired) = (semaphore.clone(), called.clone(), acquired.clone());
+            let handle = thread::spawn(move || {
+                called.lock().unwrap().push(id);
+                semaphore.acquire();
+                acquired.lock().unwrap().push(id);
+                // Hands the permit over to the next waiter.
+                semaphore.release();
+            });
+            // Gives the thread time to join the queue before the next one.
+            thread::sleep(Duration::from_millis(30));
+            handle
+        })
+        .collect();
+
+    assert!(semaphore.has_waiters());
+    semaphore.release();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(*acquired.lock().unwrap(), *called.lock().unwrap());
+    assert_eq!(semaphore.available_permits(), 1);
+}
+
+#[test]
+fn test_permits_bound_concurrency() {
+    const PERMITS: u32 = 3;
+
+    let semaphore = FairSemaphore::new(PERMITS);
+    let inside = AtomicU32::new(0);
+    thread::scope(|s| {
+        for _ in 0..8 {
+            s.spawn(|| {
+                for _ in 0..1_000 {
+                    let _guard = semaphore.acquire_guard();
+                    assert!(inside.fetch_add(1, Ordering::SeqCst) < PERMITS);
+                    thread::yield_now();
+                    inside.fetch_sub(1, Ordering::SeqCst);
+                }
+            });
+        }
+    });
+    assert_eq!(semaphore.available_permits(), PERMITS);
+}
@@ -0,0 +1,78 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    sync::atomic::{AtomicU32, Ordering},
+    thread,
+    time::Duration,
+};
+
+use omango_sync::futex_condvar::FutexCondVar;
+
+#[test]
+fn test_predicate_already_false() {
+    let condvar = FutexCondVar::new();
+    let atom = AtomicU32::new(1);
+    condvar.wait_while(&atom, |value| value == 0);
+}
+
+#[test]
+fn test_notify_all_until_predicate_fails() {
+    let condvar = FutexCondVar::new();
+    let atom = AtomicU32::new(0);
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..4)
+            .map(|_| scope.spawn(|| condvar.wait_while(&atom, |value| value < 3)))
+            .collect();
+
+        // The waiters go back to sleep while the value is still below the target.
+        for value in 1..3 {
+            thread::sleep(Duration::from_millis(20));
+            condvar.notify_all(&atom, value);
+        }
+        thread::sleep(Duration::from_millis(20));
+        assert!(handles.iter().all(|handle| !handle.is_finished()));
+
+        condvar.notify_all(&atom, 3);
+    });
+}
+
+#[test]
+fn test_notify_one_as_a_lock() {
+    // A minimal lock built on the condition variable: 0 is free, 1 is held.
+    let condvar = FutexCondVar::new();
+    let atom = AtomicU32::new(0);
+    let counter = AtomicU32::new(0);
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..1_000 {
+                    while atom.compare_exchange(0, 1, Ordering::Acquire, Ordering::Relaxed).is_err() {
+                        condvar.wait_while(&atom, |value| value == 1);
+                    }
+                    let value = counter.load(Ordering::Relaxed);
+                    counter.store(value + 1, Ordering::Relaxed);
+                    condvar.notify_one(&atom, 0);
+                }
+            });
+        }
+    });
+    assert_eq!(counter.load(Ordering::Relaxed), 4_000);
+}
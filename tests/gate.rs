@@ -0,0 +1,77 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicU64, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+use omango_sync::gate::Gate;
+
+#[test]
+fn test_wait_blocks_while_closed() {
+    let gate = Arc::new(Gate::new(false));
+    let gate_clone = gate.clone();
+    let handle = thread::spawn(move || gate_clone.wait());
+
+    thread::sleep(Duration::from_millis(30));
+    assert!(!handle.is_finished());
+    gate.open();
+    handle.join().unwrap();
+
+    // An open gate lets everyone through.
+    assert!(gate.is_open());
+    gate.wait();
+    gate.close();
+    assert!(!gate.is_open());
+}
+
+#[test]
+fn test_rapid_cycling() {
+    const WAITERS: usize = 4;
+
+    let gate = Gate::new(false);
+    let done = AtomicBool::new(false);
+    let passes = AtomicU64::new(0);
+    thread::scope(|scope| {
+        for _ in 0..WAITERS {
+            scope.spawn(|| {
+                while !done.load(Ordering::SeqCst) {
+                    gate.wait();
+                    passes.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+
+        for _ in 0..2_000 {
+            gate.open();
+            thread::yield_now();
+            gate.close();
+        }
+        // Waiters blocked on the last close must not be stuck.
+        done.store(true, Ordering::SeqCst);
+        gate.open();
+    });
+    assert!(passes.load(Ordering::Relaxed) >= WAITERS as u64);
+}
@@ -0,0 +1,194 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    mem::ManuallyDrop,
+    ptr,
+    sync::atomic::{AtomicPtr, AtomicUsize, Ordering},
+    thread,
+};
+
+use omango_sync::hazard::HazardDomain;
+
+static FREED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe fn free_box<T>(ptr: *mut T) {
+    drop(unsafe { Box::from_raw(ptr) });
+    FREED.fetch_add(1, Ordering::SeqCst);
+}
+
+#[test]
+fn test_null_protection_owns_its_slot() {
+    let domain = HazardDomain::new();
+    let value = Box::into_raw(Box::new(1));
+
+    let mut first = domain.protect::<i32>(ptr::null());
+    // The null record still owns its slot, so this one gets another one.
+    let second = domain.protect(value as *const i32);
+    assert_eq!(domain.slot_count(), 2);
+
+    first.reset(ptr::null());
+    assert!(domain.is_protected(value));
+    drop(second);
+    assert!(!domain.is_protected(value));
+    drop(first);
+    drop(unsafe { Box::from_raw(value) });
+}
+
+#[test]
+fn test_slots_grow_and_are_reused() {
+    let domain = HazardDomain::new();
+    let values: Vec<_> = (0..300).collect();
+    let records: Vec<_> = values.iter().map(|value| domain.protect(value)).collect();
+    assert_eq!(domain.slot_count(), 300);
+    assert!(values.iter().all(|value| domain.is_protected(value)));
+    drop(records);
+
+    let _record = domain.protect(&values[0]);
+    assert_eq!(domain.slot_count(), 300);
+}
+
+#[test]
+fn test_retire_waits_for_protection() {
+    let domain = HazardDomain::new();
+    let value = Box::into_raw(Box::new(String::from("value")));
+    let record = domain.protect(value as *const String);
+
+    let freed = FREED.load(Ordering::SeqCst);
+    unsafe { domain.retire(value, free_box::<String>) };
+    domain.flush();
+    assert_eq!(domain.retired_count(), 1);
+    assert_eq!(unsafe { record.as_ref() }, "value");
+
+    drop(record);
+    domain.flush();
+    assert_eq!(domain.retired_count(), 0);
+    assert!(FREED.load(Ordering::SeqCst) > freed);
+}
+
+struct Node<T> {
+    value: ManuallyDrop<T>,
+    next: *mut Node<T>,
+}
+
+unsafe impl<T: Send> Send for Node<T> {}
+
+/// A lock-free stack whose popped nodes are reclaimed with hazard pointers.
+struct TreiberStack<T> {
+    head: AtomicPtr<Node<T>>,
+    domain: HazardDomain,
+}
+
+unsafe impl<T: Send> Sync for TreiberStack<T> {}
+
+impl<T: Send + 'static> TreiberStack<T> {
+    fn new() -> Self {
+        Self {
+            head: AtomicPtr::new(ptr::null_mut()),
+            domain: HazardDomain::new(),
+        }
+    }
+
+    fn push(&self, value: T) {
+        let node = Box::into_raw(Box::new(Node {
+            value: ManuallyDrop::new(value),
+            next: ptr::null_mut(),
+        }));
+        let mut head = self.head.load(Ordering::Relaxed);
+        loop {
+            unsafe { (*node).next = head };
+            match self.head.compare_exchange_weak(head, node, Ordering::Release, Ordering::Relaxed) {
+                Ok(_) => return,
+                Err(current) => head = current,
+            }
+        }
+    }
+
+    fn pop(&self) -> Option<T> {
+        loop {
+            let record = self.domain.protect_load(&self.head);
+            let head = record.as_ptr() as *mut Node<T>;
+            if head.is_null() {
+                return None;
+            }
+            // The protection keeps the node alive even if another thread pops it.
+            let next = unsafe { (*head).next };
+            if self
+                .head
+                .compare_exchange(head, next, Ordering::AcqRel, Ordering::Relaxed)
+                .is_ok()
+            {
+                drop(record);
+                let value = unsafe { ptr::read(&*(*head).value) };
+                unsafe { self.domain.retire(head, free_node::<T>) };
+                return Some(value);
+            }
+        }
+    }
+}
+
+impl<T> Drop for TreiberStack<T> {
+    fn drop(&mut self) {
+        let mut node = *self.head.get_mut();
+        while !node.is_null() {
+            let mut boxed = unsafe { Box::from_raw(node) };
+            unsafe { ManuallyDrop::drop(&mut boxed.value) };
+            node = boxed.next;
+        }
+    }
+}
+
+unsafe fn free_node<T>(node: *mut Node<T>) {
+    // The value was moved out by the pop.
+    drop(unsafe { Box::from_raw(node) });
+}
+
+#[test]
+fn test_treiber_stack_stress() {
+    let threads: usize = if cfg!(miri) { 3 } else { 8 };
+    let ops: usize = if cfg!(miri) { 50 } else { 20_000 };
+
+    let stack = TreiberStack::new();
+    let popped = thread::scope(|scope| {
+        let handles: Vec<_> = (0..threads)
+            .map(|id| {
+                let stack = &stack;
+                scope.spawn(move || {
+                    let mut sum = 0;
+                    for i in 0..ops {
+                        stack.push(Box::new(id * ops + i));
+                        if let Some(value) = stack.pop() {
+                            sum += *value;
+                        }
+                    }
+                    sum
+                })
+            })
+            .collect();
+        handles.into_iter().map(|handle| handle.join().unwrap()).sum::<usize>()
+    });
+
+    let mut rest = 0;
+    while let Some(value) = stack.pop() {
+        rest += *value;
+    }
+    let total = threads * ops;
+    assert_eq!(popped + rest, total * (total - 1) / 2);
+}
@@ -0,0 +1,83 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{sync::Arc, thread, time::Duration};
+
+use omango_sync::latch::CountdownLatch;
+
+#[test]
+fn test_count_down() {
+    let latch = CountdownLatch::new(2);
+    assert_eq!(latch.count(), 2);
+    assert!(!latch.is_open());
+
+    latch.count_down();
+    latch.count_down();
+    assert_eq!(latch.count(), 0);
+    assert!(latch.is_open());
+
+    // Counting down an opened latch is a no-op.
+    latch.count_down();
+    assert_eq!(latch.count(), 0);
+    assert!(latch.is_open());
+}
+
+#[test]
+fn test_wakes_blocked_threads() {
+    let latch = Arc::new(CountdownLatch::new(3));
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let latch = latch.clone();
+            thread::spawn(move || latch.await_zero())
+        })
+        .collect();
+
+    for _ in 0..3 {
+        thread::sleep(Duration::from_millis(20));
+        assert!(handles.iter().all(|handle| !handle.is_finished()));
+        latch.count_down();
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+fn test_stays_open() {
+    let latch = CountdownLatch::new(1);
+    latch.count_down();
+    latch.await_zero();
+    latch.await_zero();
+
+    let latch = CountdownLatch::new(0);
+    assert!(latch.is_open());
+    latch.await_zero();
+}
+
+#[test]
+fn test_count_above_i32_max() {
+    let latch = CountdownLatch::new(u32::MAX);
+    assert_eq!(latch.count(), u32::MAX);
+    assert!(!latch.is_open());
+
+    latch.count_down();
+    assert_eq!(latch.count(), u32::MAX - 1);
+    assert!(!latch.is_open());
+}
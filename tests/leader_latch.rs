@@ -0,0 +1,102 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+use omango_sync::leader_latch::LeaderLatch;
+
+#[test]
+fn test_single_leader() {
+    let latch = LeaderLatch::new();
+    assert!(!latch.has_leader());
+    assert!(latch.try_become_leader());
+    assert!(latch.is_leader());
+    assert!(!latch.try_become_leader());
+
+    thread::scope(|s| {
+        s.spawn(|| {
+            assert!(!latch.is_leader());
+            // Only the leader can relinquish.
+            latch.relinquish();
+            assert!(latch.has_leader());
+        });
+    });
+
+    latch.relinquish();
+    assert!(!latch.has_leader());
+    assert!(!latch.is_leader());
+}
+
+#[test]
+fn test_await_leadership() {
+    let latch = LeaderLatch::new();
+    assert!(latch.try_become_leader());
+    thread::scope(|s| {
+        let follower = s.spawn(|| {
+            latch.await_leadership();
+            assert!(latch.is_leader());
+            latch.relinquish();
+        });
+        thread::sleep(Duration::from_millis(50));
+        assert!(!follower.is_finished());
+        latch.relinquish();
+    });
+    assert!(!latch.has_leader());
+}
+
+#[test]
+fn test_no_two_leaders() {
+    const THREADS: usize = 4;
+    const ROUNDS: usize = 2_000;
+
+    let latch = LeaderLatch::new();
+    let leaders = AtomicUsize::new(0);
+    let terms = AtomicUsize::new(0);
+    thread::scope(|s| {
+        for id in 0..THREADS {
+            let (latch, leaders, terms) = (&latch, &leaders, &terms);
+            s.spawn(move || {
+                for round in 0..ROUNDS {
+                    // Half of the threads poll, the others park until it is their turn.
+                    if id % 2 == 0 {
+                        while !latch.try_become_leader() {
+                            thread::yield_now();
+                        }
+                    } else {
+                        latch.await_leadership();
+                    }
+                    assert_eq!(leaders.fetch_add(1, Ordering::SeqCst), 0);
+                    assert!(latch.is_leader());
+                    if round % 100 == 0 {
+                        thread::yield_now();
+                    }
+                    leaders.fetch_sub(1, Ordering::SeqCst);
+                    terms.fetch_add(1, Ordering::Relaxed);
+                    latch.relinquish();
+                }
+            });
+        }
+    });
+    assert_eq!(terms.into_inner(), THREADS * ROUNDS);
+    assert!(!latch.has_leader());
+}
@@ -0,0 +1,118 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+// Model checks the deque with loom, run it with:
+// RUSTFLAGS="--cfg loom" cargo test --release --test loom_work_stealing
+#![cfg(loom)]
+
+use loom::thread;
+
+use omango_sync::work_stealing::{Steal, WorkStealingDeque};
+
+/// Steals until the deque is observed empty.
+fn steal_all<T: Send>(stealer: &omango_sync::work_stealing::Stealer<T>) -> Vec<T> {
+    let mut items = Vec::new();
+    loop {
+        match stealer.steal() {
+            Steal::Success(value) => items.push(value),
+            Steal::Empty => return items,
+            Steal::Retry => thread::yield_now(),
+        }
+    }
+}
+
+#[test]
+fn test_pop_races_steal() {
+    loom::model(|| {
+        let deque = WorkStealingDeque::new();
+        deque.push(1);
+        deque.push(2);
+
+        let stealer = deque.stealer();
+        let handle = thread::spawn(move || steal_all(&stealer));
+
+        let mut items = Vec::new();
+        while let Some(value) = deque.pop() {
+            items.push(value);
+        }
+        items.extend(handle.join().unwrap());
+
+        // Every item is taken exactly once, by either side.
+        items.sort_unstable();
+        assert_eq!(items, vec![1, 2]);
+    });
+}
+
+#[test]
+fn test_two_stealers() {
+    loom::model(|| {
+        let deque = WorkStealingDeque::new();
+        deque.push(1);
+        deque.push(2);
+
+        let handles: Vec<_> = (0..2)
+            .map(|_| {
+                let stealer = deque.stealer();
+                thread::spawn(move || steal_all(&stealer))
+            })
+            .collect();
+
+        let mut items: Vec<_> = handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect();
+        items.extend(deque.pop());
+        items.sort_unstable();
+        assert_eq!(items, vec![1, 2]);
+    });
+}
+
+#[test]
+fn test_steal_during_growth() {
+    // The growth and the hazard scan need more than the small default stack of the loom threads.
+    const STACK_SIZE: usize = 1 << 20;
+
+    let mut builder = loom::model::Builder::new();
+    builder.preemption_bound = Some(2);
+    builder.check(|| {
+        let owner = thread::Builder::new().stack_size(STACK_SIZE).spawn(|| {
+            let deque = WorkStealingDeque::with_capacity(32);
+            deque.push(Box::new(0));
+
+            let stealer = deque.stealer();
+            let handle = thread::Builder::new()
+                .stack_size(STACK_SIZE)
+                .spawn(move || match stealer.steal() {
+                    Steal::Success(value) => Some(value),
+                    _ => None,
+                })
+                .unwrap();
+
+            // The 33rd item makes the owner grow the buffer while the stealer may read the old one.
+            for value in 1..33 {
+                deque.push(Box::new(value));
+            }
+            let mut items: Vec<_> = std::iter::from_fn(|| deque.pop()).map(|value| *value).collect();
+            items.extend(handle.join().unwrap().into_iter().map(|value| *value));
+            items
+        }).unwrap();
+
+        let mut items = owner.join().unwrap();
+        items.sort_unstable();
+        assert_eq!(items, (0..33).collect::<Vec<_>>());
+    });
+}
@@ -0,0 +1,80 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{sync::Arc, thread, time::Duration};
+
+use omango_sync::manual_reset_event::ManualResetEvent;
+
+fn spawn_waiters(event: &Arc<ManualResetEvent>, count: usize) -> Vec<thread::JoinHandle<()>> {
+    (0..count)
+        .map(|_| {
+            let event = event.clone();
+            thread::spawn(move || event.wait())
+        })
+        .collect()
+}
+
+#[test]
+fn test_set_releases_all_waiters() {
+    let event = Arc::new(ManualResetEvent::new(false));
+    let handles = spawn_waiters(&event, 4);
+    thread::sleep(Duration::from_millis(30));
+    assert!(handles.iter().all(|handle| !handle.is_finished()));
+
+    event.set();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    // The event stays open for the later waits.
+    assert!(event.is_set());
+    event.wait();
+    event.wait();
+}
+
+#[test]
+fn test_reset_closes_again() {
+    let event = Arc::new(ManualResetEvent::new(true));
+    event.wait();
+    event.reset();
+    assert!(!event.is_set());
+
+    let handles = spawn_waiters(&event, 1);
+    thread::sleep(Duration::from_millis(30));
+    assert!(!handles[0].is_finished());
+    event.set();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
+
+#[test]
+fn test_set_then_immediate_reset() {
+    // The waiters are released by the set even if they only run after the reset.
+    for _ in 0..50 {
+        let event = Arc::new(ManualResetEvent::new(false));
+        let handles = spawn_waiters(&event, 4);
+        thread::sleep(Duration::from_millis(2));
+        event.set();
+        event.reset();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+    }
+}
@@ -0,0 +1,96 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+use omango_sync::mcs_lock::{MCSLock, MCSNode};
+
+#[test]
+fn test_try_with_lock() {
+    let lock = MCSLock::new();
+    assert_eq!(lock.try_with_lock(|| 1), Some(1));
+    lock.with_lock(|| {
+        assert!(lock.is_locked());
+        assert_eq!(lock.try_with_lock(|| 2), None);
+    });
+    assert!(!lock.is_locked());
+}
+
+#[test]
+fn test_mutual_exclusion() {
+    const THREADS: usize = 8;
+    const ITERS: u64 = 2_000;
+
+    let lock = MCSLock::new();
+    // The non-atomic read-modify-write loses updates without mutual exclusion.
+    let counter = AtomicU64::new(0);
+    let per_thread: Vec<_> = (0..THREADS).map(|_| AtomicU64::new(0)).collect();
+    thread::scope(|scope| {
+        for done in per_thread.iter() {
+            let (lock, counter) = (&lock, &counter);
+            scope.spawn(move || {
+                for _ in 0..ITERS {
+                    lock.with_lock(|| {
+                        let value = counter.load(Ordering::Relaxed);
+                        counter.store(value + 1, Ordering::Relaxed);
+                    });
+                    done.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+    assert_eq!(counter.load(Ordering::Relaxed), THREADS as u64 * ITERS);
+    // No thread starves, every one of them completes its iterations.
+    assert!(per_thread.iter().all(|done| done.load(Ordering::Relaxed) == ITERS));
+}
+
+#[test]
+fn test_fifo_order() {
+    const THREADS: usize = 8;
+
+    let lock = Arc::new(MCSLock::new());
+    let order = Arc::new(Mutex::new(Vec::new()));
+    let mut node = MCSNode::new();
+    let guard = unsafe { lock.lock(&mut node) };
+
+    let handles: Vec<_> = (0..THREADS)
+        .map(|id| {
+            let lock = lock.clone();
+            let order = order.clone();
+            let handle = thread::spawn(move || lock.with_lock(|| order.lock().unwrap().push(id)));
+            // Gives the thread time to join the queue before the next one.
+            thread::sleep(Duration::from_millis(20));
+            handle
+        })
+        .collect();
+
+    drop(guard);
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(*order.lock().unwrap(), (0..THREADS).collect::<Vec<_>>());
+}
@@ -0,0 +1,118 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+use std::{
+    sync::Mutex,
+    thread,
+    time::Duration,
+};
+
+use omango_sync::monotonic::MonotonicCounter;
+
+#[test]
+fn test_increment() {
+    let counter = MonotonicCounter::new(5);
+    assert_eq!(counter.read(), 5);
+    assert_eq!(counter.increment(), 6);
+    assert_eq!(counter.increment(), 7);
+    assert_eq!(counter.read(), 7);
+}
+
+#[test]
+fn test_concurrent_increments_are_unique() {
+    const THREADS: usize = 8;
+    const INCREMENTS: usize = 10_000;
+
+    let counter = MonotonicCounter::default();
+    let seen = Mutex::new(Vec::new());
+    thread::scope(|s| {
+        for _ in 0..THREADS {
+            s.spawn(|| {
+                let mut values = Vec::with_capacity(INCREMENTS);
+                let mut last = 0;
+                for _ in 0..INCREMENTS {
+                    let value = counter.increment();
+                    // The values returned to one thread only go up.
+                    assert!(value > last);
+                    last = value;
+                    values.push(value);
+                }
+                seen.lock().unwrap().extend(values);
+            });
+        }
+    });
+
+    let mut seen = seen.into_inner().unwrap();
+    seen.sort_unstable();
+    let expected: Vec<_> = (1..=(THREADS * INCREMENTS) as u64).collect();
+    assert_eq!(seen, expected);
+    assert_eq!(counter.read(), (THREADS * INCREMENTS) as u64);
+}
+
+#[test]
+fn test_wait_for_reached() {
+    let counter = MonotonicCounter::new(10);
+    counter.wait_for(3);
+    assert!(counter.wait_for_timeout(10, Duration::from_millis(1)));
+}
+
+#[test]
+fn test_wait_for_wakes_on_increment() {
+    let counter = MonotonicCounter::default();
+    thread::scope(|s| {
+        let waiter = s.spawn(|| {
+            counter.wait_for(5);
+            assert!(counter.read() >= 5);
+        });
+
+        for _ in 0..5 {
+            thread::sleep(Duration::from_millis(10));
+            counter.increment();
+        }
+        waiter.join().unwrap();
+    });
+}
+
+#[test]
+fn test_many_waiters() {
+    let counter = MonotonicCounter::default();
+    thread::scope(|s| {
+        for target in 1..=8 {
+            let counter = &counter;
+            s.spawn(move || counter.wait_for(target));
+        }
+        thread::sleep(Duration::from_millis(20));
+        for _ in 0..8 {
+            counter.increment();
+        }
+    });
+}
+
+#[test]
+fn test_wait_for_timeout() {
+    let counter = MonotonicCounter::default();
+    assert!(!counter.wait_for_timeout(1, Duration::from_millis(20)));
+
+    thread::scope(|s| {
+        let waiter = s.spawn(|| counter.wait_for_timeout(1, Duration::from_secs(10)));
+        thread::sleep(Duration::from_millis(20));
+        counter.increment();
+        assert!(waiter.join().unwrap());
+    });
+}
@@ -0,0 +1,133 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::Cell,
+    panic::{self, AssertUnwindSafe},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+use omango_sync::mutex::{Mutex, MutexGuard};
+
+// Compiles only if the type does not implement the trait,
+// otherwise the call is ambiguous between the two impls.
+macro_rules! assert_not_impl {
+    ($ty:ty: $tr:path) => {{
+        trait AmbiguousIfImpl<A> {
+            fn some_item() {}
+        }
+        impl<T: ?Sized> AmbiguousIfImpl<()> for T {}
+        impl<T: ?Sized + $tr> AmbiguousIfImpl<u8> for T {}
+        <$ty as AmbiguousIfImpl<_>>::some_item()
+    }};
+}
+
+fn assert_sync<T: Sync>() {}
+
+#[test]
+fn test_guard_auto_traits() {
+    assert_sync::<MutexGuard<'static, i32>>();
+    assert_not_impl!(MutexGuard<'static, i32>: Send);
+    assert_not_impl!(MutexGuard<'static, Cell<i32>>: Sync);
+}
+
+#[test]
+fn test_lock() {
+    let mutex = Mutex::new(0);
+    *mutex.lock() += 1;
+    assert_eq!(*mutex.lock(), 1);
+    assert_eq!(mutex.into_inner(), 1);
+}
+
+#[test]
+fn test_try_lock_contended() {
+    let mutex = Arc::new(Mutex::new(()));
+    let guard = mutex.lock();
+
+    let mutex_clone = mutex.clone();
+    let locked = thread::spawn(move || mutex_clone.try_lock().is_some()).join().unwrap();
+    assert!(!locked);
+
+    drop(guard);
+    assert!(mutex.try_lock().is_some());
+}
+
+#[test]
+fn test_concurrent_increment() {
+    let mutex = Arc::new(Mutex::new(0u64));
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let mutex = mutex.clone();
+            thread::spawn(move || {
+                for _ in 0..10_000 {
+                    *mutex.lock() += 1;
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(*mutex.lock(), 80_000);
+}
+
+#[test]
+fn test_lock_wakes_parked_waiter() {
+    let mutex = Arc::new(Mutex::new(0));
+    let guard = mutex.lock();
+
+    let mutex_clone = mutex.clone();
+    let handle = thread::spawn(move || *mutex_clone.lock() += 1);
+    thread::sleep(Duration::from_millis(50));
+    assert!(!handle.is_finished());
+
+    drop(guard);
+    handle.join().unwrap();
+    assert_eq!(*mutex.lock(), 1);
+}
+
+#[test]
+fn test_poison() {
+    let mutex = Arc::new(Mutex::new(0));
+    let mutex_clone = mutex.clone();
+    let result = thread::spawn(move || {
+        let mut guard = mutex_clone.lock();
+        *guard = 1;
+        panic!("panic inside the critical section");
+    }).join();
+    assert!(result.is_err());
+
+    // The lock is released by the unwinding and reports the panic.
+    assert!(mutex.is_poisoned());
+    assert_eq!(*mutex.lock(), 1);
+
+    mutex.clear_poison();
+    assert!(!mutex.is_poisoned());
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let _guard = mutex.lock();
+        panic!("second panic");
+    }));
+    assert!(result.is_err());
+    assert!(mutex.is_poisoned());
+    assert!(mutex.try_lock().is_some());
+}
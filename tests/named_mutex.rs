@@ -0,0 +1,120 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+use std::{cell::Cell, thread};
+
+use omango_sync::named_mutex::{NamedMutex, NamedMutexGuard};
+
+// Compiles only if the type does not implement the trait,
+// otherwise the call is ambiguous between the two impls.
+macro_rules! assert_not_impl {
+    ($ty:ty: $tr:path) => {{
+        trait AmbiguousIfImpl<A> {
+            fn some_item() {}
+        }
+        impl<T: ?Sized> AmbiguousIfImpl<()> for T {}
+        impl<T: ?Sized + $tr> AmbiguousIfImpl<u8> for T {}
+        <$ty as AmbiguousIfImpl<_>>::some_item()
+    }};
+}
+
+fn assert_sync<T: Sync>() {}
+
+#[test]
+fn test_guard_auto_traits() {
+    assert_sync::<NamedMutexGuard<'static, i32>>();
+    assert_not_impl!(NamedMutexGuard<'static, i32>: Send);
+    assert_not_impl!(NamedMutexGuard<'static, Cell<i32>>: Sync);
+}
+
+#[test]
+fn test_lock() {
+    let mutex = NamedMutex::new("test_lock", 0);
+    assert_eq!(mutex.name(), "test_lock");
+    *mutex.lock() += 1;
+
+    let guard = mutex.try_lock().unwrap();
+    assert_eq!(*guard, 1);
+    thread::scope(|s| {
+        assert!(s.spawn(|| mutex.try_lock().is_none()).join().unwrap());
+    });
+    drop(guard);
+    assert_eq!(mutex.into_inner(), 1);
+}
+
+#[test]
+fn test_consistent_order() {
+    let first = NamedMutex::new("test_consistent_order::first", 0);
+    let second = NamedMutex::new("test_consistent_order::second", 0);
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..1_000 {
+                    let mut a = first.lock();
+                    let mut b = second.lock();
+                    *a += 1;
+                    *b += 1;
+                }
+            });
+        }
+    });
+    assert_eq!(*first.lock(), 4_000);
+    assert_eq!(*second.lock(), 4_000);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_relock_panics() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    let mutex = NamedMutex::new("test_relock_panics", ());
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let _guard = mutex.lock();
+        let _again = mutex.lock();
+    }));
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    assert_eq!(message, "mutex \"test_relock_panics\" is already held by the current thread");
+
+    // The unwinding released the lock and the tracking.
+    drop(mutex.lock());
+}
+
+#[cfg(debug_assertions)]
+#[test]
+fn test_cycle_panics() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    let a = NamedMutex::new("test_cycle_panics::a", ());
+    let b = NamedMutex::new("test_cycle_panics::b", ());
+    {
+        let _a = a.lock();
+        let _b = b.lock();
+    }
+
+    // The reverse order closes the cycle, it is reported even though nothing is contended.
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        let _b = b.lock();
+        let _a = a.lock();
+    }));
+    let message = *result.unwrap_err().downcast::<String>().unwrap();
+    assert_eq!(
+        message,
+        "lock order cycle detected: test_cycle_panics::b -> test_cycle_panics::a -> test_cycle_panics::b",
+    );
+}
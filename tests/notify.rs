@@ -0,0 +1,65 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{sync::Arc, thread, time::Duration};
+
+use omango_sync::notify::Notify;
+
+#[test]
+fn test_stored_permits() {
+    let notify = Notify::new();
+    for _ in 0..5 {
+        notify.notify_one();
+    }
+    // Each stored permit lets one wait return immediately.
+    for _ in 0..5 {
+        notify.wait();
+    }
+    assert!(!notify.try_wait());
+}
+
+#[test]
+fn test_notify_one_wakes_waiter() {
+    let notify = Arc::new(Notify::new());
+    let notify_clone = notify.clone();
+    let handle = thread::spawn(move || notify_clone.wait());
+
+    thread::sleep(Duration::from_millis(50));
+    assert!(!handle.is_finished());
+    notify.notify_one();
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_notify_all() {
+    let notify = Arc::new(Notify::new());
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let notify = notify.clone();
+            thread::spawn(move || notify.wait())
+        })
+        .collect();
+
+    thread::sleep(Duration::from_millis(20));
+    notify.notify_all();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+}
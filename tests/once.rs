@@ -0,0 +1,67 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+use omango_sync::once::OnceLock;
+
+#[test]
+fn test_get_and_set() {
+    let once = OnceLock::new();
+    assert_eq!(once.get(), None);
+    assert_eq!(once.set(1), Ok(()));
+    assert_eq!(once.set(2), Err(2));
+    assert_eq!(once.get(), Some(&1));
+    assert_eq!(*once.get_or_init(|| 3), 1);
+    assert_eq!(once.into_inner(), Some(1));
+}
+
+#[test]
+fn test_concurrent_init_runs_once() {
+    let once = Arc::new(OnceLock::new());
+    let calls = Arc::new(AtomicU32::new(0));
+
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            let once = once.clone();
+            let calls = calls.clone();
+            thread::spawn(move || {
+                *once.get_or_init(|| {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    // Keep the others waiting for the initialization.
+                    thread::sleep(Duration::from_millis(50));
+                    i
+                })
+            })
+        })
+        .collect();
+    let values: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert!(values.iter().all(|value| *value == values[0]));
+    assert_eq!(once.get(), Some(&values[0]));
+}
@@ -0,0 +1,87 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    panic,
+    sync::{
+        Arc, Barrier,
+        atomic::{AtomicU32, Ordering},
+    },
+    thread,
+};
+
+use omango_sync::once_cell::OnceCell;
+
+#[test]
+fn test_concurrent_init() {
+    const THREADS: usize = 8;
+
+    let cell = Arc::new(OnceCell::new());
+    let calls = Arc::new(AtomicU32::new(0));
+    let barrier = Arc::new(Barrier::new(THREADS));
+    let handles: Vec<_> = (0..THREADS)
+        .map(|id| {
+            let cell = cell.clone();
+            let calls = calls.clone();
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                barrier.wait();
+                *cell.get_or_init(|| {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    id
+                })
+            })
+        })
+        .collect();
+
+    let values: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert!(values.iter().all(|value| Some(value) == cell.get()));
+}
+
+#[test]
+fn test_panic_recovery() {
+    let cell = OnceCell::new();
+    let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+        cell.get_or_init(|| panic!("init failed"));
+    }));
+    assert!(result.is_err());
+    assert_eq!(cell.get(), None);
+
+    // A later call can still initialize the cell.
+    assert_eq!(*cell.get_or_init(|| 5), 5);
+}
+
+#[test]
+fn test_try_init_error() {
+    let cell = OnceCell::new();
+    assert_eq!(cell.get_or_try_init(|| Err("error")), Err("error"));
+    assert_eq!(cell.get(), None);
+    assert_eq!(cell.get_or_try_init(|| Ok::<_, ()>(1)), Ok(&1));
+}
+
+#[test]
+fn test_set() {
+    let cell = OnceCell::new();
+    assert_eq!(cell.set(1), Ok(()));
+    assert_eq!(cell.set(2), Err(2));
+    assert_eq!(cell.get(), Some(&1));
+    assert_eq!(cell.into_inner(), Some(1));
+}
@@ -0,0 +1,55 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    mem,
+    sync::atomic::{AtomicU32, AtomicU64, Ordering},
+    thread,
+};
+
+use omango_sync::padded::PaddedAtomic;
+
+#[test]
+fn test_layout() {
+    assert_eq!(mem::align_of::<PaddedAtomic<AtomicU32>>(), 64);
+    assert_eq!(mem::size_of::<[PaddedAtomic<AtomicU64>; 4]>(), 4 * 64);
+    assert!(PaddedAtomic::<AtomicU64>::is_padding_needed());
+    assert!(!PaddedAtomic::<[u8; 64]>::is_padding_needed());
+
+    let slots = [const { PaddedAtomic::<AtomicU64>::new(0) }; 2];
+    let first = &*slots[0] as *const AtomicU64 as usize;
+    let second = &*slots[1] as *const AtomicU64 as usize;
+    assert_eq!(second - first, 64);
+}
+
+#[test]
+fn test_independent_increments() {
+    let slots = [const { PaddedAtomic::<AtomicU64>::new(0) }; 8];
+    thread::scope(|scope| {
+        for slot in slots.iter() {
+            scope.spawn(move || {
+                for _ in 0..10_000 {
+                    slot.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+    assert!(slots.iter().all(|slot| slot.load(Ordering::Relaxed) == 10_000));
+}
@@ -0,0 +1,71 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use omango_sync::parker::parking;
+
+#[test]
+fn test_tokens_coalesce() {
+    let (parker, unparker) = parking();
+    unparker.unpark();
+    unparker.unpark();
+    unparker.clone().unpark();
+
+    // All unparks before the park make one token.
+    parker.park();
+    assert!(!parker.park_timeout(Duration::from_millis(20)));
+}
+
+#[test]
+fn test_park_timeout() {
+    let (parker, _unparker) = parking();
+    let start = Instant::now();
+    assert!(!parker.park_timeout(Duration::from_millis(50)));
+    assert!(start.elapsed() >= Duration::from_millis(50));
+
+    parker.unparker().unpark();
+    assert!(parker.park_timeout(Duration::from_secs(10)));
+}
+
+#[test]
+fn test_unpark_from_other_thread() {
+    let (parker, unparker) = parking();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        let sent = Instant::now();
+        unparker.unpark();
+        sent
+    });
+
+    assert!(parker.park_timeout(Duration::from_secs(10)));
+    let woken = Instant::now();
+    let sent = handle.join().unwrap();
+    assert!(woken.duration_since(sent) < Duration::from_millis(100));
+}
+
+#[test]
+fn test_unparker_is_send_sync() {
+    fn assert_send_sync<T: Clone + Send + Sync>() {}
+    assert_send_sync::<omango_sync::parker::Unparker>();
+}
@@ -0,0 +1,87 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    sync::atomic::{AtomicU32, AtomicU64, Ordering},
+    thread,
+};
+
+use omango_sync::phaser::Phaser;
+
+#[test]
+fn test_dynamic_deregistration() {
+    // Worker `w` leaves after phase `w`, the last one stays registered.
+    const WORKERS: usize = 4;
+    const PARTICIPANTS: [u32; 3] = [4, 3, 2];
+
+    let phaser = Phaser::new();
+    for id in 0..WORKERS {
+        assert_eq!(phaser.register(), id);
+    }
+    let arrivals: [AtomicU32; 3] = Default::default();
+
+    thread::scope(|scope| {
+        for worker in 0..WORKERS {
+            let (phaser, arrivals) = (&phaser, &arrivals);
+            scope.spawn(move || {
+                for phase in 0..3 {
+                    arrivals[phase].fetch_add(1, Ordering::SeqCst);
+                    if phase == worker {
+                        assert_eq!(phaser.arrive_and_deregister(), phase as u32);
+                        return;
+                    }
+                    assert_eq!(phaser.arrive_and_await_advance(), phase as u32 + 1);
+                    // Everyone of the phase has arrived before it advances.
+                    assert_eq!(arrivals[phase].load(Ordering::SeqCst), PARTICIPANTS[phase]);
+                }
+            });
+        }
+    });
+
+    assert_eq!(phaser.phase(), 3);
+    assert_eq!(phaser.registered(), 1);
+    assert_eq!(phaser.unarrived(), 1);
+    assert_eq!(phaser.arrived(), 0);
+}
+
+#[test]
+fn test_no_early_release() {
+    const PARTIES: u64 = 3;
+    const PHASES: u64 = 2_000;
+
+    let phaser = Phaser::new();
+    let arrivals = AtomicU64::new(0);
+    for _ in 0..PARTIES {
+        phaser.register();
+    }
+    thread::scope(|scope| {
+        for _ in 0..PARTIES {
+            scope.spawn(|| {
+                for phase in 0..PHASES {
+                    arrivals.fetch_add(1, Ordering::SeqCst);
+                    assert_eq!(phaser.arrive_and_await_advance(), phase as u32 + 1);
+                    assert!(arrivals.load(Ordering::SeqCst) >= (phase + 1) * PARTIES);
+                    assert!(phaser.phase() > phase as u32);
+                }
+            });
+        }
+    });
+    assert_eq!(phaser.phase(), PHASES as u32);
+}
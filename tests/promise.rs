@@ -0,0 +1,69 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{thread, time::Duration};
+
+use omango_sync::{
+    error::{Error, ErrorKind},
+    promise::promise,
+};
+
+#[test]
+fn test_resolve_from_other_thread() {
+    let (promise, future) = promise();
+    let handle = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(20));
+        promise.resolve(String::from("value"));
+    });
+    assert_eq!(future.get().unwrap(), "value");
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_reject() {
+    let (promise, future) = promise::<u32>();
+    promise.reject(Error::new(ErrorKind::UserError, "rejected"));
+    let err = future.get().unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::UserError);
+    assert_eq!(err.message, "rejected");
+}
+
+#[test]
+fn test_dropped_promise() {
+    let (promise, future) = promise::<u32>();
+    let handle = thread::spawn(move || drop(promise));
+    let err = future.get().unwrap_err();
+    assert!(err.is_cancelled());
+    assert_eq!(err.message, "promise dropped");
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_try_get() {
+    let (promise, future) = promise();
+    assert!(future.try_get().is_none());
+    assert!(!future.is_ready());
+    promise.resolve(1);
+    assert!(future.is_ready());
+    assert_eq!(future.try_get().unwrap().unwrap(), 1);
+    // The result was moved out already.
+    assert!(future.try_get().is_none());
+    assert!(future.get().is_err());
+}
@@ -0,0 +1,92 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::Cell,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+use omango_sync::reentrant_mutex::ReentrantMutex;
+
+#[test]
+fn test_nested_lock() {
+    let mutex = ReentrantMutex::new(Cell::new(0));
+    let first = mutex.lock();
+    let second = mutex.lock();
+    let third = mutex.lock();
+    third.set(third.get() + 1);
+    assert_eq!(first.get(), 1);
+    drop(third);
+    drop(second);
+    assert!(mutex.try_lock().is_some());
+    drop(first);
+}
+
+#[test]
+fn test_outer_drop_wakes_blocked_thread() {
+    let mutex = Arc::new(ReentrantMutex::new(Cell::new(0)));
+    let acquired = Arc::new(AtomicBool::new(false));
+
+    let outer = mutex.lock();
+    let middle = mutex.lock();
+    let inner = mutex.lock();
+
+    let handle = {
+        let mutex = mutex.clone();
+        let acquired = acquired.clone();
+        thread::spawn(move || {
+            let guard = mutex.lock();
+            acquired.store(true, Ordering::SeqCst);
+            guard.set(guard.get() + 1);
+        })
+    };
+
+    thread::sleep(Duration::from_millis(30));
+    assert!(!acquired.load(Ordering::SeqCst));
+    drop(inner);
+    drop(middle);
+
+    // The lock is still held by the outermost guard.
+    thread::sleep(Duration::from_millis(30));
+    assert!(!acquired.load(Ordering::SeqCst));
+    outer.set(10);
+    drop(outer);
+
+    handle.join().unwrap();
+    assert!(acquired.load(Ordering::SeqCst));
+    assert_eq!(mutex.lock().get(), 11);
+}
+
+#[test]
+fn test_try_lock_from_other_thread() {
+    let mutex = Arc::new(ReentrantMutex::new(()));
+    let guard = mutex.lock();
+    let mutex_clone = mutex.clone();
+    assert!(!thread::spawn(move || mutex_clone.try_lock().is_some()).join().unwrap());
+    drop(guard);
+
+    let mutex_clone = mutex.clone();
+    assert!(thread::spawn(move || mutex_clone.try_lock().is_some()).join().unwrap());
+}
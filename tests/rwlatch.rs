@@ -0,0 +1,138 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+use std::{
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+use omango_sync::rwlatch::ReadWriteLatch;
+
+#[test]
+fn test_readers_share_the_read_phase() {
+    let latch = ReadWriteLatch::new();
+    latch.read_enter();
+    latch.read_enter();
+    assert_eq!(latch.readers(), 2);
+    assert!(!latch.is_write_phase());
+
+    latch.read_exit();
+    latch.read_exit();
+    assert_eq!(latch.readers(), 0);
+}
+
+#[test]
+fn test_flip_waits_for_readers_to_drain() {
+    let latch = ReadWriteLatch::new();
+    let reader_done = AtomicBool::new(false);
+    latch.read_enter();
+
+    thread::scope(|s| {
+        let flipper = s.spawn(|| {
+            latch.flip_to_write();
+            // The flip completes only after the reader exited.
+            assert!(reader_done.load(Ordering::SeqCst));
+        });
+
+        thread::sleep(Duration::from_millis(50));
+        assert!(!flipper.is_finished());
+        assert!(!latch.is_write_phase());
+
+        reader_done.store(true, Ordering::SeqCst);
+        latch.read_exit();
+        flipper.join().unwrap();
+    });
+    assert!(latch.is_write_phase());
+}
+
+#[test]
+fn test_readers_blocked_in_write_phase() {
+    let latch = ReadWriteLatch::new();
+    latch.flip_to_write();
+    latch.write_enter();
+
+    let writer_done = AtomicBool::new(false);
+    thread::scope(|s| {
+        let reader = s.spawn(|| {
+            latch.read_enter();
+            assert!(writer_done.load(Ordering::SeqCst));
+            latch.read_exit();
+        });
+
+        thread::sleep(Duration::from_millis(50));
+        assert!(!reader.is_finished());
+
+        writer_done.store(true, Ordering::SeqCst);
+        latch.write_exit();
+        latch.flip_to_read();
+        reader.join().unwrap();
+    });
+    assert!(!latch.is_write_phase());
+}
+
+#[test]
+fn test_single_writer_in_write_phase() {
+    let latch = ReadWriteLatch::new();
+    let inside = AtomicUsize::new(0);
+    latch.flip_to_write();
+
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                for _ in 0..1_000 {
+                    latch.write_enter();
+                    assert_eq!(inside.fetch_add(1, Ordering::SeqCst), 0);
+                    inside.fetch_sub(1, Ordering::SeqCst);
+                    latch.write_exit();
+                }
+            });
+        }
+    });
+}
+
+#[test]
+fn test_phase_cycles() {
+    let latch = ReadWriteLatch::new();
+    let stop = AtomicBool::new(false);
+    let writing = AtomicBool::new(false);
+
+    thread::scope(|s| {
+        for _ in 0..4 {
+            s.spawn(|| {
+                while !stop.load(Ordering::SeqCst) {
+                    latch.read_enter();
+                    // No reader overlaps the writer.
+                    assert!(!writing.load(Ordering::SeqCst));
+                    latch.read_exit();
+                }
+            });
+        }
+
+        for _ in 0..200 {
+            latch.flip_to_write();
+            latch.write_enter();
+            writing.store(true, Ordering::SeqCst);
+            writing.store(false, Ordering::SeqCst);
+            latch.write_exit();
+            latch.flip_to_read();
+        }
+        stop.store(true, Ordering::SeqCst);
+    });
+}
@@ -0,0 +1,129 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    sync::{
+        Arc, Barrier,
+        atomic::{AtomicBool, AtomicU32, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+use omango_sync::rwlock::RwLock;
+
+#[test]
+fn test_concurrent_readers() {
+    const READERS: usize = 4;
+
+    let lock = Arc::new(RwLock::new(5));
+    // All readers hold the lock at the same time to pass the barrier.
+    let barrier = Arc::new(Barrier::new(READERS));
+    let handles: Vec<_> = (0..READERS)
+        .map(|_| {
+            let lock = lock.clone();
+            let barrier = barrier.clone();
+            thread::spawn(move || {
+                let guard = lock.read();
+                barrier.wait();
+                *guard
+            })
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), 5);
+    }
+}
+
+#[test]
+fn test_writer_is_exclusive() {
+    let lock = RwLock::new(0);
+    {
+        let _read = lock.read();
+        assert!(lock.try_write().is_none());
+        assert!(lock.try_read().is_some());
+    }
+    {
+        let mut write = lock.write();
+        *write += 1;
+        assert!(lock.try_read().is_none());
+        assert!(lock.try_write().is_none());
+    }
+    assert_eq!(*lock.read(), 1);
+}
+
+#[test]
+fn test_concurrent_writers() {
+    let lock = Arc::new(RwLock::new((0u64, 0u64)));
+    let handles: Vec<_> = (0..8)
+        .map(|i| {
+            let lock = lock.clone();
+            thread::spawn(move || {
+                for _ in 0..5_000 {
+                    if i % 2 == 0 {
+                        let mut guard = lock.write();
+                        guard.0 += 1;
+                        guard.1 += 1;
+                    } else {
+                        let guard = lock.read();
+                        assert_eq!(guard.0, guard.1);
+                    }
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(*lock.read(), (20_000, 20_000));
+}
+
+#[test]
+fn test_writer_not_starved() {
+    let lock = Arc::new(RwLock::new(0));
+    let stop = Arc::new(AtomicBool::new(false));
+    let reads = Arc::new(AtomicU32::new(0));
+
+    // The readers overlap, so the read lock is never free without the writer preference.
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let lock = lock.clone();
+            let stop = stop.clone();
+            let reads = reads.clone();
+            thread::spawn(move || {
+                while !stop.load(Ordering::Relaxed) {
+                    let _guard = lock.read();
+                    reads.fetch_add(1, Ordering::Relaxed);
+                    thread::sleep(Duration::from_micros(100));
+                }
+            })
+        })
+        .collect();
+    while reads.load(Ordering::Relaxed) < 100 {
+        thread::yield_now();
+    }
+
+    *lock.write() = 1;
+    stop.store(true, Ordering::Relaxed);
+    for reader in readers {
+        reader.join().unwrap();
+    }
+    assert_eq!(*lock.read(), 1);
+}
@@ -0,0 +1,82 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{thread, time::Duration};
+
+use omango_sync::segmented_lock::{SegmentedLock, SegmentedRwLock};
+
+/// Returns a key of another segment and a key of the same segment as `key`.
+fn pick_keys<F: Fn(&u64) -> usize>(key: u64, segment_index: F) -> (u64, u64) {
+    let segment = segment_index(&key);
+    let other = (key + 1..).find(|k| segment_index(k) != segment).unwrap();
+    let colliding = (key + 1..).find(|k| segment_index(k) == segment).unwrap();
+    (other, colliding)
+}
+
+#[test]
+fn test_different_segments_are_independent() {
+    let lock: SegmentedLock<16> = SegmentedLock::new();
+    assert_eq!(lock.segment_count(), 16);
+    let (other, colliding) = pick_keys(0, |key| lock.segment_index(key));
+
+    let _guard = lock.lock_for(&0u64);
+    thread::scope(|scope| {
+        scope.spawn(|| {
+            assert!(lock.try_lock_for(&other).is_some());
+            assert!(lock.try_lock_for(&colliding).is_none());
+        });
+    });
+}
+
+#[test]
+fn test_colliding_keys_are_serialized() {
+    let lock: SegmentedLock<16> = SegmentedLock::new();
+    let (_, colliding) = pick_keys(0, |key| lock.segment_index(key));
+
+    let guard = lock.lock_for(&0u64);
+    thread::scope(|scope| {
+        let handle = scope.spawn(|| drop(lock.lock_for(&colliding)));
+        thread::sleep(Duration::from_millis(30));
+        assert!(!handle.is_finished());
+        drop(guard);
+    });
+}
+
+#[test]
+fn test_rwlock_segments() {
+    let lock: SegmentedRwLock<16> = SegmentedRwLock::new();
+    let (other, colliding) = pick_keys(0, |key| lock.segment_index(key));
+
+    let first = lock.read_for(&0u64);
+    thread::scope(|scope| {
+        // Readers share a segment, writers of other segments do not wait.
+        scope.spawn(|| drop(lock.read_for(&colliding)));
+        scope.spawn(|| drop(lock.write_for(&other)));
+    });
+
+    let read = lock.read_for(&colliding);
+    thread::scope(|scope| {
+        let handle = scope.spawn(|| drop(lock.write_for(&0u64)));
+        thread::sleep(Duration::from_millis(30));
+        assert!(!handle.is_finished());
+        drop(read);
+        drop(first);
+    });
+}
@@ -0,0 +1,99 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    },
+    thread,
+    time::Duration,
+};
+
+use omango_sync::semaphore::Semaphore;
+
+#[test]
+fn test_try_acquire() {
+    let sem = Semaphore::new(2);
+    assert!(sem.try_acquire());
+    assert!(sem.try_acquire());
+    assert!(!sem.try_acquire());
+    assert_eq!(sem.available_permits(), 0);
+
+    sem.release();
+    assert_eq!(sem.available_permits(), 1);
+    assert!(sem.try_acquire());
+}
+
+#[test]
+fn test_guard_releases_permit() {
+    let sem = Semaphore::new(1);
+    {
+        let _guard = sem.acquire_guard();
+        assert!(!sem.try_acquire());
+    }
+    assert_eq!(sem.available_permits(), 1);
+}
+
+#[test]
+fn test_acquire_blocks_until_release() {
+    let sem = Arc::new(Semaphore::new(0));
+    let sem_clone = sem.clone();
+    let handle = thread::spawn(move || sem_clone.acquire());
+
+    thread::sleep(Duration::from_millis(50));
+    assert!(!handle.is_finished());
+    sem.release();
+    handle.join().unwrap();
+    assert_eq!(sem.available_permits(), 0);
+}
+
+#[test]
+fn test_max_concurrency() {
+    const PERMITS: u32 = 4;
+
+    let sem = Arc::new(Semaphore::new(PERMITS));
+    let gauge = Arc::new(AtomicU32::new(0));
+    let max = Arc::new(AtomicU32::new(0));
+
+    let handles: Vec<_> = (0..16)
+        .map(|_| {
+            let sem = sem.clone();
+            let gauge = gauge.clone();
+            let max = max.clone();
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    let _guard = sem.acquire_guard();
+                    let running = gauge.fetch_add(1, Ordering::SeqCst) + 1;
+                    assert!(running <= PERMITS);
+                    max.fetch_max(running, Ordering::SeqCst);
+                    thread::yield_now();
+                    gauge.fetch_sub(1, Ordering::SeqCst);
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    assert!(max.load(Ordering::SeqCst) <= PERMITS);
+    assert_eq!(sem.available_permits(), PERMITS);
+}
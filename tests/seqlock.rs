@@ -0,0 +1,75 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+};
+
+use omango_sync::seqlock::SeqLock;
+
+#[test]
+fn test_read_write() {
+    let lock = SeqLock::new((1, 2));
+    lock.write(|value| value.0 += 10);
+    assert_eq!(lock.read(), (11, 2));
+    lock.store((3, 4));
+    assert_eq!(lock.try_read(), Some((3, 4)));
+    assert_eq!(lock.into_inner(), (3, 4));
+}
+
+#[test]
+fn test_reads_are_consistent() {
+    // Readers must never observe a half-written value.
+    let lock = SeqLock::new([0u64; 8]);
+    let done = AtomicBool::new(false);
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                let mut last = 0;
+                while !done.load(Ordering::Relaxed) {
+                    let value = lock.read();
+                    assert!(value.iter().all(|&item| item == value[0]));
+                    // Writes are never seen out of order.
+                    assert!(value[0] >= last);
+                    last = value[0];
+                }
+            });
+        }
+        for _ in 0..2 {
+            scope.spawn(|| {
+                for _ in 0..20_000 {
+                    lock.write(|value| {
+                        let next = value[0] + 1;
+                        for item in value.iter_mut() {
+                            *item = next;
+                        }
+                    });
+                }
+            });
+        }
+        // Keeps the readers running until both writers are finished.
+        while lock.read()[0] != 40_000 {
+            thread::yield_now();
+        }
+        done.store(true, Ordering::Relaxed);
+    });
+    assert_eq!(lock.read(), [40_000; 8]);
+}
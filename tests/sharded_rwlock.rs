@@ -0,0 +1,71 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{collections::HashMap, thread};
+
+use omango_sync::sharded_rwlock::ShardedRwLock;
+
+#[test]
+fn test_shard_index_wraps() {
+    let lock: ShardedRwLock<u32, 4> = ShardedRwLock::new();
+    assert_eq!(lock.shard_count(), 4);
+    *lock.write(1) = 7;
+    assert_eq!(*lock.read(5), 7);
+    assert!(std::ptr::eq(lock.shard(2), lock.shard(6)));
+    assert!(std::ptr::eq(lock.shard_for("key"), lock.shard_for("key")));
+}
+
+#[test]
+fn test_write_all() {
+    let lock: ShardedRwLock<u32, 8> = ShardedRwLock::from_fn(|idx| idx as u32);
+    let mut guards = lock.write_all();
+    assert_eq!(guards.len(), 8);
+    for guard in guards.iter_mut() {
+        **guard += 1;
+    }
+    drop(guards);
+    assert_eq!((0..8).map(|idx| *lock.read(idx)).sum::<u32>(), 36);
+}
+
+#[test]
+fn test_concurrent_readers_and_writers() {
+    let lock: ShardedRwLock<HashMap<u64, u64>, 16> = ShardedRwLock::new();
+    thread::scope(|scope| {
+        for id in 0..2u64 {
+            let lock = &lock;
+            scope.spawn(move || {
+                for key in (id..1000).step_by(2) {
+                    lock.write(key as usize).insert(key, key * 2);
+                }
+            });
+        }
+        for _ in 0..8 {
+            let lock = &lock;
+            scope.spawn(move || {
+                for key in 0..1000u64 {
+                    if let Some(value) = lock.read(key as usize).get(&key) {
+                        assert_eq!(*value, key * 2);
+                    }
+                }
+            });
+        }
+    });
+    assert_eq!((0..16).map(|idx| lock.read(idx).len()).sum::<usize>(), 1000);
+}
@@ -0,0 +1,51 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    sync::atomic::{AtomicU32, Ordering},
+    thread,
+};
+
+use omango_sync::spin_barrier::SpinBarrier;
+
+#[test]
+fn test_one_leader_per_cycle() {
+    const THREADS: u32 = 4;
+    const CYCLES: u32 = 100;
+
+    let barrier = SpinBarrier::new(THREADS);
+    let leaders = AtomicU32::new(0);
+    let arrived = AtomicU32::new(0);
+    thread::scope(|scope| {
+        for _ in 0..THREADS {
+            scope.spawn(|| {
+                for cycle in 0..CYCLES {
+                    arrived.fetch_add(1, Ordering::SeqCst);
+                    if barrier.wait().is_leader() {
+                        leaders.fetch_add(1, Ordering::SeqCst);
+                    }
+                    // Nobody passes before everyone of this cycle has arrived.
+                    assert!(arrived.load(Ordering::SeqCst) >= (cycle + 1) * THREADS);
+                }
+            });
+        }
+    });
+    assert_eq!(leaders.load(Ordering::SeqCst), CYCLES);
+}
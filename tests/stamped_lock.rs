@@ -0,0 +1,102 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+use std::{
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    thread,
+    time::Duration,
+};
+
+use omango_sync::stamped_lock::StampedLock;
+
+#[test]
+fn test_optimistic_read() {
+    let lock = StampedLock::new();
+    let stamp = lock.optimistic_read();
+    assert_eq!(stamp % 2, 0);
+    assert!(lock.validate(stamp));
+}
+
+#[test]
+fn test_write_invalidates_stamp() {
+    let lock = StampedLock::new();
+    let stamp = lock.optimistic_read();
+
+    let (write_stamp, guard) = lock.write_lock();
+    assert_eq!(write_stamp % 2, 1);
+    assert!(lock.is_write_locked());
+    // A stamp taken during a write is never valid.
+    assert!(!lock.validate(lock.optimistic_read()));
+    drop(guard);
+
+    assert!(!lock.is_write_locked());
+    assert!(!lock.validate(stamp));
+    assert_eq!(lock.optimistic_read(), stamp + 2);
+}
+
+#[test]
+fn test_read_lock_is_shared() {
+    let lock = StampedLock::new();
+    let (stamp, _first) = lock.read_lock();
+    let (other, _second) = lock.read_lock();
+    assert_eq!(stamp, other);
+    assert!(lock.validate(stamp));
+}
+
+#[test]
+fn test_write_waits_for_readers() {
+    let lock = StampedLock::new();
+    let (_, guard) = lock.read_lock();
+    let written = AtomicBool::new(false);
+    thread::scope(|s| {
+        s.spawn(|| {
+            let _guard = lock.write_lock();
+            written.store(true, Ordering::SeqCst);
+        });
+        thread::sleep(Duration::from_millis(50));
+        assert!(!written.load(Ordering::SeqCst));
+        drop(guard);
+    });
+    assert!(written.into_inner());
+}
+
+#[test]
+fn test_read_is_consistent_under_writes() {
+    // The writer keeps both halves equal, a validated read must never see them differ.
+    let lock = StampedLock::new();
+    let (first, second) = (AtomicU64::new(0), AtomicU64::new(0));
+    let stop = AtomicBool::new(false);
+    thread::scope(|s| {
+        for _ in 0..3 {
+            s.spawn(|| {
+                while !stop.load(Ordering::SeqCst) {
+                    let (a, b) = lock.read(|| (first.load(Ordering::Relaxed), second.load(Ordering::Relaxed)));
+                    assert_eq!(a, b);
+                }
+            });
+        }
+
+        for i in 1..=10_000 {
+            let _guard = lock.write_lock();
+            first.store(i, Ordering::Relaxed);
+            second.store(i, Ordering::Relaxed);
+        }
+        stop.store(true, Ordering::SeqCst);
+    });
+}
@@ -0,0 +1,75 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    cell::Cell,
+    thread,
+    time::{Duration, Instant},
+};
+
+use omango_sync::throttle::{Throttle, ThrottleMode, ThrottleResult};
+
+#[test]
+fn test_skip_mode() {
+    let throttle = Throttle::new(Duration::from_millis(50));
+    let calls = Cell::new(0);
+    let call = || calls.set(calls.get() + 1);
+
+    assert_eq!(throttle.call(call, ThrottleMode::Skip), ThrottleResult::Executed);
+    assert_eq!(throttle.call(call, ThrottleMode::Skip), ThrottleResult::Skipped);
+    assert_eq!(calls.get(), 1);
+
+    thread::sleep(Duration::from_millis(60));
+    assert_eq!(throttle.call(call, ThrottleMode::Skip), ThrottleResult::Executed);
+    assert_eq!(calls.get(), 2);
+}
+
+#[test]
+fn test_block_mode() {
+    let interval = Duration::from_millis(20);
+    let throttle = Throttle::new(interval);
+    let mut times = Vec::new();
+    for _ in 0..5 {
+        let result = throttle.call(|| times.push(Instant::now()), ThrottleMode::Block);
+        assert_eq!(result, ThrottleResult::Executed);
+    }
+    // Rapid calls are spread to at most one per interval.
+    for pair in times.windows(2) {
+        assert!(pair[1] - pair[0] >= interval);
+    }
+}
+
+#[test]
+fn test_block_mode_concurrent() {
+    let interval = Duration::from_millis(10);
+    let throttle = Throttle::new(interval);
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..3 {
+                    throttle.call(|| {}, ThrottleMode::Block);
+                }
+            });
+        }
+    });
+    // 12 calls, the first runs at once.
+    assert!(start.elapsed() >= interval * 11);
+}
@@ -0,0 +1,58 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    thread,
+    time::{Duration, Instant},
+};
+
+use omango_sync::token_bucket::TokenBucket;
+
+#[test]
+fn test_try_acquire() {
+    let bucket = TokenBucket::new(5, 0.001);
+    assert!(bucket.try_acquire(3));
+    assert!(bucket.try_acquire(2));
+    assert!(!bucket.try_acquire(1));
+    assert_eq!(bucket.available(), 0);
+}
+
+#[test]
+fn test_rate_under_contention() {
+    const THREADS: u32 = 4;
+    const PER_THREAD: u32 = 30;
+
+    // Starts with 10 tokens and gains one per millisecond,
+    // the 110 remaining tokens need at least 110ms.
+    let bucket = TokenBucket::new(10, 1.0);
+    let start = Instant::now();
+    thread::scope(|scope| {
+        for _ in 0..THREADS {
+            scope.spawn(|| {
+                for _ in 0..PER_THREAD {
+                    bucket.acquire(1);
+                }
+            });
+        }
+    });
+    let elapsed = start.elapsed();
+    assert!(elapsed >= Duration::from_millis(105), "{:?}", elapsed);
+    assert!(elapsed < Duration::from_secs(5), "{:?}", elapsed);
+}
@@ -0,0 +1,94 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::thread;
+
+use omango_util::lock::RwSpinlock;
+
+use omango_sync::{
+    fair_mutex::FairMutex,
+    mutex::Mutex,
+    rwlock::RwLock,
+    ticket_lock::TicketLock,
+    traits::{Lock, LockShared, ReadLock, TryLock, WriteLock},
+};
+
+fn with_lock<L: Lock, R>(lock: &L, f: impl FnOnce(&mut L::Data) -> R) -> R {
+    f(&mut lock.lock())
+}
+
+fn increment_concurrently<L: Lock<Data = u64> + Sync>(lock: &L) -> u64 {
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..1_000 {
+                    with_lock(lock, |value| *value += 1);
+                }
+            });
+        }
+    });
+    with_lock(lock, |value| *value)
+}
+
+fn check_try_lock<L: TryLock>(lock: &L) {
+    let guard = lock.try_lock().unwrap();
+    assert!(lock.try_lock().is_none());
+    drop(guard);
+    assert!(lock.try_lock().is_some());
+}
+
+fn read<L: LockShared>(lock: &L) -> L::Data
+where
+    L::Data: Copy + PartialEq + std::fmt::Debug,
+{
+    let first = lock.lock_shared();
+    // Shared guards can be held together.
+    let second = lock.lock_shared();
+    assert_eq!(*first, *second);
+    *first
+}
+
+#[test]
+fn test_generic_lock() {
+    assert_eq!(increment_concurrently(&RwSpinlock::new(0)), 4_000);
+    assert_eq!(increment_concurrently(&Mutex::new(0)), 4_000);
+    assert_eq!(increment_concurrently(&FairMutex::new(0)), 4_000);
+    assert_eq!(increment_concurrently(&TicketLock::new(0)), 4_000);
+    assert_eq!(increment_concurrently(&RwLock::new(0)), 4_000);
+}
+
+#[test]
+fn test_generic_try_lock() {
+    check_try_lock(&RwSpinlock::new(0));
+    check_try_lock(&Mutex::new(0));
+    check_try_lock(&RwLock::new(0));
+}
+
+#[test]
+fn test_rwlock_sides() {
+    let lock = RwLock::new(1);
+    with_lock(&WriteLock::new(&lock), |value| *value = 5);
+    assert_eq!(read(&ReadLock::new(&lock)), 5);
+    assert_eq!(read(&lock), 5);
+    assert_eq!(read(&RwSpinlock::new(7)), 7);
+
+    let _read = lock.lock_shared();
+    assert!(WriteLock::new(&lock).try_lock().is_none());
+}
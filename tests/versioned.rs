@@ -0,0 +1,78 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{
+    sync::atomic::{AtomicU64, Ordering},
+    thread,
+};
+
+use omango_sync::versioned::VersionedCell;
+
+#[test]
+fn test_compare_and_update() {
+    let cell = VersionedCell::new(String::from("a"));
+    assert_eq!(cell.read(), (String::from("a"), 0));
+    assert!(cell.compare_and_update(0, String::from("b")));
+    // The version has moved on, the stale update is rejected.
+    assert!(!cell.compare_and_update(0, String::from("c")));
+    assert_eq!(cell.read(), (String::from("b"), 1));
+}
+
+#[test]
+fn test_concurrent_compare_and_update() {
+    const THREADS: usize = 8;
+    const ATTEMPTS: usize = 2_000;
+
+    let cell = VersionedCell::new(0u64);
+    let successes = AtomicU64::new(0);
+    thread::scope(|scope| {
+        for _ in 0..THREADS {
+            scope.spawn(|| {
+                for _ in 0..ATTEMPTS {
+                    let (value, version) = cell.read();
+                    if cell.compare_and_update(version, value + 1) {
+                        successes.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            });
+        }
+    });
+
+    let successes = successes.load(Ordering::Relaxed);
+    assert!(successes > 0);
+    assert_eq!(cell.version(), successes);
+    // No successful update was based on a stale value.
+    assert_eq!(cell.read().0, successes);
+}
+
+#[test]
+fn test_update_retries() {
+    let cell = VersionedCell::new(0u64);
+    thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| {
+                for _ in 0..1_000 {
+                    cell.update(|value| value + 1);
+                }
+            });
+        }
+    });
+    assert_eq!(cell.read(), (8_000, 8_000));
+}
@@ -0,0 +1,79 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::{sync::Barrier, thread};
+
+use omango_sync::watch::channel;
+
+#[test]
+fn test_try_recv() {
+    let (sender, receiver) = channel();
+    assert_eq!(receiver.try_recv(), None);
+
+    sender.send(1);
+    sender.send(2);
+    assert!(receiver.has_changed());
+    // Only the latest value is kept.
+    assert_eq!(receiver.try_recv(), Some(2));
+    assert_eq!(receiver.try_recv(), None);
+    assert!(!receiver.has_changed());
+}
+
+#[test]
+fn test_receivers_see_every_value() {
+    const RECEIVERS: usize = 4;
+    const VALUES: u32 = 100;
+
+    let (sender, receiver) = channel();
+    // The sender waits for all receivers before sending the next value.
+    let barrier = Barrier::new(RECEIVERS + 1);
+    thread::scope(|scope| {
+        let handles: Vec<_> = (0..RECEIVERS)
+            .map(|_| {
+                let receiver = receiver.clone();
+                let barrier = &barrier;
+                scope.spawn(move || {
+                    let mut seen = Vec::new();
+                    for _ in 0..VALUES {
+                        seen.push(receiver.recv());
+                        barrier.wait();
+                    }
+                    seen
+                })
+            })
+            .collect();
+
+        for value in 0..VALUES {
+            sender.send(value);
+            barrier.wait();
+        }
+        for handle in handles {
+            assert_eq!(handle.join().unwrap(), (0..VALUES).collect::<Vec<_>>());
+        }
+    });
+}
+
+#[test]
+fn test_subscribe() {
+    let (sender, _receiver) = channel();
+    sender.send("a");
+    let receiver = sender.subscribe();
+    assert_eq!(receiver.recv(), "a");
+}
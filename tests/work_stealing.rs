@@ -0,0 +1,121 @@
+// Copyright (c) 2024 Trung Tran <tqtrungse@gmail.com>
+//
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+//
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+//
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    thread,
+};
+
+use omango_sync::work_stealing::{Steal, WorkStealingDeque};
+
+#[test]
+fn test_push_pop_lifo() {
+    let deque = WorkStealingDeque::new();
+    for i in 0..3 {
+        deque.push(i);
+    }
+    assert_eq!(deque.len(), 3);
+    assert_eq!(deque.pop(), Some(2));
+    assert_eq!(deque.pop(), Some(1));
+    assert_eq!(deque.pop(), Some(0));
+    assert_eq!(deque.pop(), None);
+    assert!(deque.is_empty());
+}
+
+#[test]
+fn test_steal_fifo() {
+    let deque = WorkStealingDeque::new();
+    let stealer = deque.stealer();
+    assert_eq!(stealer.steal(), Steal::Empty);
+
+    deque.push(1);
+    deque.push(2);
+    assert_eq!(stealer.steal(), Steal::Success(1));
+    assert_eq!(deque.pop(), Some(2));
+    assert!(stealer.is_empty());
+}
+
+#[test]
+fn test_grow_keeps_items() {
+    let deque = WorkStealingDeque::with_capacity(32);
+    for i in 0..1000 {
+        deque.push(i);
+    }
+    let stealer = deque.stealer();
+    assert_eq!(stealer.steal(), Steal::Success(0));
+    assert_eq!(deque.pop(), Some(999));
+    assert_eq!(deque.len(), 998);
+}
+
+#[test]
+fn test_drop_remaining_items() {
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+    struct Tracked;
+
+    impl Drop for Tracked {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let deque = WorkStealingDeque::new();
+    for _ in 0..100 {
+        deque.push(Tracked);
+    }
+    drop(deque.pop());
+    drop(deque);
+    assert_eq!(DROPS.load(Ordering::Relaxed), 100);
+}
+
+#[test]
+fn test_concurrent_steal() {
+    const ITEMS: usize = 100_000;
+    const STEALERS: usize = 4;
+
+    let deque = WorkStealingDeque::new();
+    let sum = AtomicUsize::new(0);
+    let taken = AtomicUsize::new(0);
+    thread::scope(|s| {
+        for _ in 0..STEALERS {
+            let stealer = deque.stealer();
+            let (sum, taken) = (&sum, &taken);
+            s.spawn(move || {
+                while taken.load(Ordering::Relaxed) < ITEMS {
+                    if let Steal::Success(value) = stealer.steal() {
+                        sum.fetch_add(value, Ordering::Relaxed);
+                        taken.fetch_add(1, Ordering::Relaxed);
+                    }
+                }
+            });
+        }
+
+        for i in 0..ITEMS {
+            deque.push(i);
+            if i % 3 == 0 {
+                if let Some(value) = deque.pop() {
+                    sum.fetch_add(value, Ordering::Relaxed);
+                    taken.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+    });
+    // Every item is taken exactly once.
+    assert_eq!(sum.into_inner(), ITEMS * (ITEMS - 1) / 2);
+}